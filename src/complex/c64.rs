@@ -1,11 +1,10 @@
 use std::{
     fmt::Debug,
-    ops::{Add, Mul, Neg, Sub},
+    ops::{Add, Div, Mul, Neg, Sub},
 };
 
 use approx_collections::ApproxEq;
 
-
 pub type Scalar = f64;
 
 ///complex number with f64 components. used for points and rotations
@@ -68,6 +67,13 @@ impl Sub for C64 {
     }
 }
 
+impl Div for C64 {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self::Output {
+        (1.0 / rhs.mag_sq()) * (self * rhs.conj())
+    }
+}
+
 impl ApproxEq for C64 {
     fn approx_eq(&self, other: &Self, prec: approx_collections::Precision) -> bool {
         self.re.approx_eq(&other.re, prec) && self.im.approx_eq(&other.im, prec)
@@ -113,6 +113,78 @@ impl ComplexCircle {
             angle_b.total_cmp(&angle_a)
         }
     }
+    ///the circle through three points. returns None if the points are collinear (or two of them are approx equal)
+    pub fn through(a: Point, b: Point, c: Point) -> Option<Self> {
+        let (u, v) = (b - a, c - a);
+        let d = 2.0 * (u.0.re * v.0.im - u.0.im * v.0.re);
+        if d.approx_eq_zero(PRECISION) {
+            return None;
+        }
+        //solve for the center relative to a, which is the same distance from a, b and c
+        let center = a + Vector(C64 {
+            re: (v.0.im * u.mag_sq() - u.0.im * v.mag_sq()) / d,
+            im: (u.0.re * v.mag_sq() - v.0.re * u.mag_sq()) / d,
+        });
+        Some(Self {
+            center,
+            r_sq: center.dist_sq(a),
+        })
+    }
+    ///the circle centered at a point that is tangent to this circle: from the outside if the point is outside this
+    ///circle, and from the inside if it is inside. returns None if the point is on this circle
+    pub fn tangent_at(&self, center: Point) -> Option<Self> {
+        let r = (self.center.dist(center) - self.r()).abs();
+        if r.approx_eq_zero(PRECISION) {
+            return None;
+        }
+        Some(Self {
+            center,
+            r_sq: r * r,
+        })
+    }
+    ///the circles of a radius that are tangent to both this circle and another, from the outside of both. returns
+    ///none if the radius isn't positive
+    pub fn tangent_circles(&self, other: Self, r: Scalar) -> Vec<Self> {
+        if r.is_nan() || r <= 0.0 || r.approx_eq_zero(PRECISION) {
+            return Vec::new();
+        }
+        let grow = |x: &Self| Self {
+            center: x.center,
+            r_sq: (x.r() + r) * (x.r() + r),
+        };
+        grow(self)
+            .intersect_circle(grow(&other))
+            .into_iter()
+            .map(|center| Self {
+                center,
+                r_sq: r * r,
+            })
+            .collect()
+    }
+    ///the inverse of a point in the circle, on the same ray from the center and with the product of their distances
+    ///to it equal to r_sq. returns None for the center, whose inverse is infinity
+    pub fn invert(&self, point: Point) -> Option<Point> {
+        let offset = point - self.center;
+        if offset.mag_sq().approx_eq_zero(PRECISION) {
+            return None;
+        }
+        Some(
+            self.center
+                + Vector(
+                    C64 {
+                        re: self.r_sq,
+                        im: 0.0,
+                    } / offset.0.conj(),
+                ),
+        )
+    }
+    ///reflect a circle across the line through two points. returns None if the points are approx equal
+    pub fn reflect_across(&self, a: Point, b: Point) -> Option<Self> {
+        Some(Self {
+            center: self.center.reflect_across(a, b)?,
+            r_sq: self.r_sq,
+        })
+    }
     pub fn right_point(&self) -> Point {
         self.center
             + Vector(C64 {
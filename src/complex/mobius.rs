@@ -0,0 +1,133 @@
+use approx_collections::ApproxEq;
+
+use crate::{
+    PRECISION,
+    complex::{
+        arc::Arc,
+        c64::C64,
+        complex_circle::{Circle, Contains, Orientation, OrientedCircle},
+        point::Point,
+        rotation::Rotation,
+        vector::Vector,
+    },
+};
+
+#[derive(Debug, Clone, Copy)]
+///a möbius transformation, z -> (az + b) / (cz + d). these map circles to circles, except for the circles through the
+///pole, which become lines
+pub struct Mobius {
+    pub a: C64,
+    pub b: C64,
+    pub c: C64,
+    pub d: C64,
+}
+
+impl Mobius {
+    ///the elliptic möbius transformation that rotates around a pivot inside a circle, mapping the circle (and the disk
+    ///inside it) to itself. it fixes the pivot and the inverse of the pivot in the circle, and near the pivot it looks
+    ///like the rotation. at the center of the circle it is just the rotation. returns None if the pivot isn't properly
+    ///inside the circle
+    pub fn elliptic(circle: Circle, pivot: Point, rot: Rotation) -> Option<Self> {
+        if circle.contains(pivot) != Contains::Inside {
+            return None;
+        }
+        let (center, p, k) = (circle.center.0, pivot.0, rot.0);
+        let one = C64 { re: 1.0, im: 0.0 };
+        //the other fixed point is the inverse of the pivot in the circle, which is infinity at the center
+        let Some(q) = circle.invert(pivot) else {
+            return Some(Self {
+                a: k,
+                b: center - (k * center),
+                c: C64 { re: 0.0, im: 0.0 },
+                d: one,
+            });
+        };
+        let q = q.0;
+        //conjugate the rotation by z -> (z - p) / (z - q), which sends the pivot to 0 and the inverse to infinity
+        Some(Self {
+            a: p - (k * q),
+            b: p * q * (k - one),
+            c: one - k,
+            d: (k * p) - q,
+        })
+    }
+    ///apply the transformation to a point. the pole is sent off to infinity, so it should not be passed
+    pub fn point(&self, point: Point) -> Point {
+        Point(((self.a * point.0) + self.b) / ((self.c * point.0) + self.d))
+    }
+    ///the point sent to infinity, or None if the transformation doesn't move infinity
+    pub fn pole(&self) -> Option<Point> {
+        if self.c.approx_eq(&C64 { re: 0.0, im: 0.0 }, PRECISION) {
+            None
+        } else {
+            Some(Point(-self.d / self.c))
+        }
+    }
+    ///whether the transformation sends the inside of a circle to the outside of its image, which happens when the
+    ///pole is inside the circle
+    pub fn flips(&self, circle: Circle) -> bool {
+        self.pole()
+            .is_some_and(|x| circle.contains(x) == Contains::Inside)
+    }
+    ///apply the transformation to a circle, exactly. the center of the image is the image of the inverse of the pole in
+    ///the circle. returns None if the circle goes through the pole, since its image is a line
+    pub fn circle(&self, circle: Circle) -> Option<Circle> {
+        let on = self.point(
+            circle.center
+                + Vector(C64 {
+                    re: circle.r(),
+                    im: 0.0,
+                }),
+        );
+        let center = match self.pole() {
+            None => self.point(circle.center),
+            Some(pole) if circle.contains(pole) == Contains::Border => return None,
+            //the inverse of the center is infinity, which is sent to a / c
+            Some(pole) => match circle.invert(pole) {
+                Some(inverse) => self.point(inverse),
+                None => Point(self.a / self.c),
+            },
+        };
+        Some(Circle {
+            center,
+            r_sq: center.dist_sq(on),
+        })
+    }
+    ///apply the transformation to an oriented circle, flipping its orientation if the inside is sent to the outside
+    pub fn oriented_circle(&self, circle: OrientedCircle) -> Option<OrientedCircle> {
+        let image = OrientedCircle {
+            circ: self.circle(circle.circ)?,
+            ori: circle.ori,
+        };
+        Some(if self.flips(circle.circ) {
+            -image
+        } else {
+            image
+        })
+    }
+    ///apply the transformation to an arc. the arc goes around its image the other way if the inside of its circle is
+    ///sent to the outside. returns None if the arc's circle goes through the pole
+    pub fn arc(&self, arc: Arc) -> Option<Arc> {
+        let circle = self.circle(arc.circle)?;
+        let start = self.point(arc.start);
+        let flipped = self.flips(arc.circle);
+        //whole circles have the same start and end, so keep going all the way around
+        if arc.start.approx_eq(&arc.end(), PRECISION) {
+            return Some(Arc {
+                circle,
+                start,
+                angle: if flipped { -arc.angle } else { arc.angle },
+            });
+        }
+        let ori = match (arc.orientation(), flipped) {
+            (Orientation::CCW, false) | (Orientation::CW, true) => Orientation::CCW,
+            (Orientation::CW, false) | (Orientation::CCW, true) => Orientation::CW,
+        };
+        Some(Arc::from_endpoints(
+            circle,
+            start,
+            self.point(arc.end()),
+            ori,
+        ))
+    }
+}
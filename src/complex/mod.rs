@@ -1,6 +1,9 @@
 pub mod arc;
 pub mod c64;
 pub mod complex_circle;
+pub mod mobius;
 pub mod point;
 pub mod rotation;
+pub mod sphere;
+pub mod symmetry;
 pub mod vector;
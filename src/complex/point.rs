@@ -25,6 +25,16 @@ impl Point {
     pub fn dist(&self, other: Self) -> Scalar {
         (*self - other).mag()
     }
+    ///the point halfway between this point and another
+    pub fn midpoint(&self, other: Self) -> Self {
+        *self + (0.5 * (other - *self))
+    }
+    ///reflect the point across the line through two points. returns None if the points are approx equal
+    pub fn reflect_across(&self, a: Self, b: Self) -> Option<Self> {
+        let dir = (b - a).normalize()?.0;
+        //rotate the line onto the real axis, conjugate, and rotate back
+        Some(a + Vector(dir * dir * (*self - a).0.conj()))
+    }
 }
 impl ApproxEq for Point {
     fn approx_eq(&self, other: &Self, prec: approx_collections::Precision) -> bool {
@@ -0,0 +1,122 @@
+use approx_collections::ApproxEqZero;
+
+use crate::{
+    PRECISION,
+    complex::{
+        c64::{C64, Scalar},
+        complex_circle::{ComplexCircle, Contains, OrientedCircle},
+        point::Point,
+    },
+};
+
+#[derive(Debug, Clone, Copy)]
+///a point on the unit sphere. spherical puzzles are stored as their stereographic projection from the south pole, so
+///the northern hemisphere is the unit disk, and the projection looks like the sphere seen from the outside. a puzzle
+///projected from another pole is turned to put that pole at the bottom first (see from_pole)
+pub struct SpherePoint {
+    pub x: Scalar,
+    pub y: Scalar,
+    pub z: Scalar,
+}
+
+impl SpherePoint {
+    ///the point spherical puzzles are projected from, unless they declare another one
+    pub const SOUTH_POLE: Self = Self {
+        x: 0.0,
+        y: 0.0,
+        z: -1.0,
+    };
+    ///the point of the sphere in a direction. returns None for the zero vector
+    pub fn from_direction(x: Scalar, y: Scalar, z: Scalar) -> Option<Self> {
+        let mag = (x * x + y * y + z * z).sqrt();
+        if mag.approx_eq_zero(PRECISION) {
+            return None;
+        }
+        Some(Self {
+            x: x / mag,
+            y: y / mag,
+            z: z / mag,
+        })
+    }
+    ///the point of the sphere projected to a point of the plane (the inverse stereographic projection)
+    pub fn from_point(point: Point) -> Self {
+        let s = point.0.mag_sq();
+        Self {
+            x: 2.0 * point.0.re / (1.0 + s),
+            y: 2.0 * point.0.im / (1.0 + s),
+            z: (1.0 - s) / (1.0 + s),
+        }
+    }
+    ///the stereographic projection of the point. returns None for the south pole, which is projected to infinity
+    pub fn to_point(&self) -> Option<Point> {
+        if (1.0 + self.z).approx_eq_zero(PRECISION) {
+            return None;
+        }
+        Some(Point(C64 {
+            re: self.x / (1.0 + self.z),
+            im: self.y / (1.0 + self.z),
+        }))
+    }
+    ///the point on the other side of the sphere
+    pub fn antipode(&self) -> Self {
+        Self {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+    ///the point moved by the rotation of the sphere taking a pole to the south pole, so that projecting the moved point
+    ///is projecting from the pole. the rotation is around the axis perpendicular to both poles (rodrigues' formula),
+    ///or half a turn around the x axis for the north pole
+    pub fn from_pole(&self, pole: Self) -> Self {
+        let c = -pole.z;
+        if (1.0 + c).approx_eq_zero(PRECISION) {
+            return Self {
+                x: self.x,
+                y: -self.y,
+                z: -self.z,
+            };
+        }
+        //the cross product of the pole and the south pole, whose length is the sine of the angle between them
+        let (kx, ky) = (-pole.y, pole.x);
+        let d = (kx * self.x + ky * self.y) / (1.0 + c);
+        Self {
+            x: self.x * c + ky * self.z + kx * d,
+            y: self.y * c - kx * self.z + ky * d,
+            z: self.z * c + kx * self.y - ky * self.x,
+        }
+    }
+    ///the straight line distance between two points of the sphere
+    pub fn dist(&self, other: &Self) -> Scalar {
+        ((self.x - other.x).powi(2) + (self.y - other.y).powi(2) + (self.z - other.z).powi(2))
+            .sqrt()
+    }
+}
+
+///the projection of the cap of the sphere around an axis, with an angular radius. the cap is the inside of the circle,
+///unless it contains the south pole, in which case it is the outside. returns None if the edge of the cap goes through
+///the south pole, since it is projected to a line
+pub fn cap(axis: SpherePoint, radius: Scalar) -> Option<OrientedCircle> {
+    //the cap is where (axis . p) > cos(radius), which projects to -(z + c)|w|^2 + 2(x re(w) + y im(w)) + (z - c) > 0.
+    //completing the square gives a circle, inside it if z + c > 0
+    let c = radius.cos();
+    let denom = axis.z + c;
+    if denom.approx_eq_zero(PRECISION) {
+        return None;
+    }
+    let center = Point(C64 {
+        re: axis.x / denom,
+        im: axis.y / denom,
+    });
+    Some(OrientedCircle {
+        circ: ComplexCircle {
+            center,
+            r_sq: (1.0 - c * c) / (denom * denom),
+        },
+        ori: if denom > 0.0 {
+            Contains::Inside
+        } else {
+            Contains::Outside
+        },
+    })
+}
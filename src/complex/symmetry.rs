@@ -0,0 +1,131 @@
+use std::f64::consts::PI;
+
+use approx_collections::ApproxEq;
+
+use crate::{
+    PRECISION,
+    complex::{
+        arc::Arc,
+        c64::{C64, Scalar},
+        complex_circle::{ComplexCircle, OrientedCircle},
+        point::Point,
+        vector::Vector,
+    },
+};
+
+#[derive(Debug, Clone, Copy)]
+///a rotation about a point, or a reflection across a line through a point
+pub enum Isometry {
+    Rotation { center: Point, angle: Scalar }, //rotation counterclockwise by the angle
+    Reflection { center: Point, angle: Scalar }, //reflection across the line through the center at the angle
+}
+
+impl Isometry {
+    ///the reflection across the line through two points. returns None if the points are approx equal
+    pub fn reflection_through(a: Point, b: Point) -> Option<Self> {
+        Some(Self::Reflection {
+            center: a,
+            angle: (b - a).normalize()?.angle(),
+        })
+    }
+    ///whether the isometry reverses orientation (which turns it reverses the direction of)
+    pub fn is_reflection(&self) -> bool {
+        matches!(self, Self::Reflection { .. })
+    }
+    ///apply the isometry to a point
+    pub fn point(&self, point: Point) -> Point {
+        match *self {
+            Self::Rotation { center, angle } => point.rotate_about(center, angle),
+            Self::Reflection { center, angle } => point
+                .reflect_across(center, center + Vector(C64::from_angle(angle)))
+                .unwrap_or(point),
+        }
+    }
+    ///apply the isometry to a circle
+    pub fn circle(&self, circle: ComplexCircle) -> ComplexCircle {
+        ComplexCircle {
+            center: self.point(circle.center),
+            r_sq: circle.r_sq,
+        }
+    }
+    ///apply the isometry to an arc. reflections reverse the direction the arc goes around its circle
+    pub fn arc(&self, arc: Arc) -> Arc {
+        Arc {
+            circle: self.circle(arc.circle),
+            start: self.point(arc.start),
+            angle: if self.is_reflection() {
+                -arc.angle
+            } else {
+                arc.angle
+            },
+        }
+    }
+    ///apply the isometry to an oriented circle, keeping its orientation
+    pub fn oriented_circle(&self, circle: OrientedCircle) -> OrientedCircle {
+        OrientedCircle {
+            circ: self.circle(circle.circ),
+            ori: circle.ori,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+///the symmetry of a puzzle about a point: cyclic (rotations only) or dihedral (rotations and reflections)
+pub struct Symmetry {
+    pub center: Point,  //the point the symmetry is about
+    pub order: usize,   //the number of rotations, including the identity
+    pub dihedral: bool, //whether the symmetry includes reflections
+    pub axis: Scalar,   //the angle of one of the lines of reflection, if there are any
+}
+
+impl Symmetry {
+    ///every element of the symmetry group: the rotations, starting with the identity, then the reflections if the
+    ///symmetry is dihedral
+    pub fn elements(&self) -> Vec<Isometry> {
+        let order = self.order.max(1);
+        let rotations = (0..order).map(|k| Isometry::Rotation {
+            center: self.center,
+            angle: 2.0 * PI * k as Scalar / order as Scalar,
+        });
+        let reflections = (0..order)
+            .filter(|_| self.dihedral)
+            .map(|k| Isometry::Reflection {
+                center: self.center,
+                angle: self.axis + PI * k as Scalar / order as Scalar,
+            });
+        rotations.chain(reflections).collect()
+    }
+    ///the same symmetry of the puzzle after it is moved by an isometry: about the moved center, with the moved lines
+    ///of reflection
+    pub fn transformed(&self, isometry: &Isometry) -> Self {
+        let axis = match *isometry {
+            Isometry::Rotation { angle, .. } => self.axis + angle,
+            Isometry::Reflection { angle, .. } => 2.0 * angle - self.axis,
+        };
+        Self {
+            center: isometry.point(self.center),
+            axis,
+            ..*self
+        }
+    }
+    ///the images of a circle under every element of the symmetry, without repeats
+    pub fn orbit_circle(&self, circle: OrientedCircle) -> Vec<OrientedCircle> {
+        let mut orbit: Vec<OrientedCircle> = Vec::new();
+        for image in self.elements().iter().map(|x| x.oriented_circle(circle)) {
+            if !orbit.iter().any(|x| x.approx_eq(&image, PRECISION)) {
+                orbit.push(image);
+            }
+        }
+        orbit
+    }
+    ///the images of a point under every element of the symmetry, without repeats
+    pub fn orbit_point(&self, point: Point) -> Vec<Point> {
+        let mut orbit: Vec<Point> = Vec::new();
+        for image in self.elements().iter().map(|x| x.point(point)) {
+            if !orbit.iter().any(|x| x.approx_eq(&image, PRECISION)) {
+                orbit.push(image);
+            }
+        }
+        orbit
+    }
+}
@@ -0,0 +1,307 @@
+use std::path::{Path, PathBuf};
+
+use crate::DEF_PATH;
+use crate::hps::data_storer::data_storer::DataStorer;
+use crate::hps::data_storer::io::{read_file_to_string, write_bytes_to_file, write_string_to_file};
+use crate::hps::data_storer::puzzle_io::PuzzleIOData;
+use crate::hps::data_storer::stats::CatalogStats;
+use crate::puzzle::animation::{AnimationOptions, encode_apng};
+use crate::puzzle::color::ColorScheme;
+use crate::puzzle::puzzle::Puzzle;
+use crate::puzzle::raster::RasterOptions;
+use crate::puzzle::svg::SvgOptions;
+
+///usage string printed when the arguments are not understood
+const USAGE: &str = "usage: circleguy [command]
+commands:
+    check                              load every definition and the keybind config, and report any problems
+    svg <puzzle> <output> [options]    write a puzzle as an svg. <puzzle> is a puzzle like \"2 Circles/Pentagons/Stars\"
+                                       or the path of a log file (ending in .kdl), to write the puzzle as it was saved
+        --solved                       write the solved state
+        --size <pixels>                the length of the longer side of the image (default 1000)
+        --outline <pixels>             the width of the outlines (default 2)
+    png <puzzle> <output> [options]    draw a puzzle to a png, without the gpu. takes the same options as svg
+                                       (the default size is 256, and the default outline is 1)
+    thumbnails [options]               draw a png thumbnail of every definition into Puzzles/Thumbnails, shown in the puzzle list.
+                                       takes the same --size and --outline options as png
+    stats [output] [options]           build every definition and write statistics about them (puzzles per author,
+                                       folder and number of circles, piece counts and the most complex puzzles) to
+                                       [output], or print them if it isn't given
+        --experimental                 include the experimental puzzles
+    animate <puzzle> <output> [turns]  write an animated png of doing a sequence of turns, like \"R U2 R'\", from the
+                                       puzzle's state (the saved state, if <puzzle> is a log file)
+        --solve                        animate the solve saved in the log file from its scramble, instead of the turns
+        --cut                          cut the puzzle along each turn
+        --frames <count>               how many frames each turn takes (default 10)
+        --fps <count>                  how many frames are shown each second (default 30)
+        --hold <milliseconds>          how long the first and last frames are shown (default 1000)
+        --size <pixels>, --outline <pixels>
+                                       the same as for png";
+
+///run a command without opening a window. returns the exit code of the process
+pub fn run(args: &[String]) -> i32 {
+    match args.first().map(String::as_str) {
+        Some("check") => check(),
+        Some("svg") => report(svg(&args[1..])),
+        Some("png") => report(png(&args[1..])),
+        Some("thumbnails") => report(thumbnails(&args[1..])),
+        Some("animate") => report(animate(&args[1..])),
+        Some("stats") => report(stats(&args[1..])),
+        _ => {
+            eprintln!("{USAGE}");
+            2
+        }
+    }
+}
+
+///print the error of a command, if any, and give the exit code
+fn report(result: Result<(), String>) -> i32 {
+    match result {
+        Ok(()) => 0,
+        Err(err) => {
+            eprintln!("{err}");
+            1
+        }
+    }
+}
+
+///load all of the definitions (including experimental ones) and the keybinds, printing every problem found.
+///returns 1 if anything was wrong
+fn check() -> i32 {
+    let mut ds = match DataStorer::new(true) {
+        Ok(ds) => ds,
+        Err(diag) => {
+            eprintln!("error creating data storer: {}", diag.msg);
+            return 1;
+        }
+    };
+    let mut problems = 0;
+    if ds.load_puzzles(DEF_PATH).is_err() {
+        eprintln!("error loading puzzle definitions from {DEF_PATH}");
+        problems += 1;
+    }
+    if ds.load_keybinds("Configs/keybinds.kdl").is_err() && ds.keybind_diagnostics.is_empty() {
+        eprintln!("error reading Configs/keybinds.kdl");
+        problems += 1;
+    }
+    for diag in &ds.keybind_diagnostics {
+        eprintln!("{diag}");
+        problems += 1;
+    }
+    let defs = ds.puzzles.lock().unwrap().defs();
+    for def in &defs {
+        let keybinds = ds.keybinds.get_keybinds_for_puzzle(def);
+        if let Err(diag) = def.load(&mut ds.rt, keybinds) {
+            eprintln!("{}: {}", def.path.to_string_lossy(), diag.msg);
+            problems += 1;
+        }
+    }
+    println!(
+        "checked {} puzzles, {} problems found",
+        defs.len(),
+        problems
+    );
+    (problems > 0) as i32
+}
+
+///make a data storer with every (non-experimental) definition and the keybinds loaded
+fn load_data_storer() -> Result<DataStorer, String> {
+    let mut ds = DataStorer::new(false).map_err(|diag| diag.msg.to_string())?;
+    ds.load_puzzles(DEF_PATH)
+        .map_err(|_| format!("error loading puzzle definitions from {DEF_PATH}"))?;
+    let _ = ds.load_keybinds("Configs/keybinds.kdl");
+    Ok(ds)
+}
+
+///load a puzzle by its name in the definition tree, or from a log file if the name ends in .kdl
+fn load_puzzle(ds: &mut DataStorer, name: &str) -> Result<Puzzle, String> {
+    if Path::new(name).extension().is_some_and(|x| x == "kdl") {
+        let data =
+            read_file_to_string(name).map_err(|err| format!("error reading {name}: {err}"))?;
+        let io_data =
+            PuzzleIOData::from_string(data).ok_or(format!("{name} is not a valid log file"))?;
+        return Puzzle::from_io_data(io_data, ds)
+            .ok_or(format!("error loading the puzzle in {name}"));
+    }
+    let def = ds
+        .puzzles
+        .lock()
+        .unwrap()
+        .get(&PathBuf::from(name))
+        .ok_or(format!("no puzzle named {name}"))?;
+    let keybinds = ds.keybinds.get_keybinds_for_puzzle(&def);
+    Ok(Puzzle::new(
+        def.load(&mut ds.rt, keybinds)
+            .map_err(|diag| diag.msg.to_string())?,
+    ))
+}
+
+///build every definition and write statistics about the catalog to a file, or print them
+fn stats(args: &[String]) -> Result<(), String> {
+    let mut experimental = false;
+    let mut positional = Vec::new();
+    for arg in args {
+        match arg.as_str() {
+            "--experimental" => experimental = true,
+            _ => positional.push(arg),
+        }
+    }
+    if positional.len() > 1 {
+        return Err(String::from(USAGE));
+    }
+    let mut ds = DataStorer::new(experimental).map_err(|diag| diag.msg.to_string())?;
+    ds.load_puzzles(DEF_PATH)
+        .map_err(|_| format!("error loading puzzle definitions from {DEF_PATH}"))?;
+    let defs = ds.puzzles.lock().unwrap().defs();
+    for def in &defs {
+        ds.count(def);
+    }
+    let report = CatalogStats::new(&defs, &ds.counts).to_string();
+    match positional.first() {
+        Some(output) => write_string_to_file(Path::new(output), &report)
+            .map_err(|err| format!("error writing {output}: {err}")),
+        None => {
+            print!("{report}");
+            Ok(())
+        }
+    }
+}
+
+///parse the value after an option like `--size`
+fn option_value(args: &mut std::slice::Iter<String>, option: &str) -> Result<f64, String> {
+    args.next()
+        .and_then(|x| x.parse().ok())
+        .ok_or(format!("{option} needs a number after it"))
+}
+
+///write a puzzle as an svg
+fn svg(args: &[String]) -> Result<(), String> {
+    let mut options = SvgOptions::default();
+    let mut positional = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--solved" => options.solved = true,
+            "--size" => options.size = option_value(&mut iter, "--size")?,
+            "--outline" => options.outline_width = option_value(&mut iter, "--outline")?,
+            _ => positional.push(arg),
+        }
+    }
+    let [name, output] = positional[..] else {
+        return Err(String::from(USAGE));
+    };
+    let mut ds = load_data_storer()?;
+    let puzzle = load_puzzle(&mut ds, name)?;
+    write_string_to_file(
+        Path::new(output),
+        &puzzle.to_svg(&options, &ColorScheme::default()),
+    )
+    .map_err(|err| format!("error writing {output}: {err}"))
+}
+
+///parse the options for drawing a png, returning the other arguments
+fn raster_options(args: &[String]) -> Result<(RasterOptions, Vec<&String>), String> {
+    let mut options = RasterOptions::default();
+    let mut positional = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--solved" => options.solved = true,
+            "--size" => options.size = option_value(&mut iter, "--size")? as u32,
+            "--outline" => options.outline_width = option_value(&mut iter, "--outline")?,
+            _ => positional.push(arg),
+        }
+    }
+    Ok((options, positional))
+}
+
+///draw a puzzle to a png
+fn png(args: &[String]) -> Result<(), String> {
+    let (options, positional) = raster_options(args)?;
+    let [name, output] = positional[..] else {
+        return Err(String::from(USAGE));
+    };
+    let mut ds = load_data_storer()?;
+    let puzzle = load_puzzle(&mut ds, name)?;
+    let png = puzzle
+        .rasterize(&options, &ColorScheme::default())
+        .to_png()?;
+    write_bytes_to_file(Path::new(output), &png)
+        .map_err(|err| format!("error writing {output}: {err}"))
+}
+
+///draw a thumbnail of every definition, printing the ones that fail
+fn thumbnails(args: &[String]) -> Result<(), String> {
+    let (options, positional) = raster_options(args)?;
+    if !positional.is_empty() {
+        return Err(String::from(USAGE));
+    }
+    let mut ds = load_data_storer()?;
+    let defs = ds.puzzles.lock().unwrap().defs();
+    let mut failed = 0;
+    for def in &defs {
+        let keybinds = ds.keybinds.get_keybinds_for_puzzle(def);
+        let result = def
+            .load(&mut ds.rt, keybinds)
+            .map_err(|diag| diag.msg.to_string())
+            .and_then(|data| {
+                Puzzle::new(data)
+                    .rasterize(&options, &ColorScheme::default())
+                    .to_png()
+            })
+            .and_then(|png| {
+                write_bytes_to_file(Path::new(&def.thumbnail_path()), &png)
+                    .map_err(|err| err.to_string())
+            });
+        if let Err(err) = result {
+            eprintln!("{}: {err}", def.key());
+            failed += 1;
+        }
+    }
+    println!("wrote {} thumbnails", defs.len() - failed);
+    if failed > 0 {
+        return Err(format!("{failed} thumbnails could not be written"));
+    }
+    Ok(())
+}
+
+///write an animated png of a sequence of turns
+fn animate(args: &[String]) -> Result<(), String> {
+    let mut options = AnimationOptions::default();
+    let mut solve = false;
+    let mut positional = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--solve" => solve = true,
+            "--cut" => options.cut = true,
+            "--frames" => options.frames_per_turn = option_value(&mut iter, "--frames")? as u32,
+            "--fps" => options.fps = option_value(&mut iter, "--fps")? as u16,
+            "--hold" => options.hold = option_value(&mut iter, "--hold")? as u16,
+            "--size" => options.raster.size = option_value(&mut iter, "--size")? as u32,
+            "--outline" => options.raster.outline_width = option_value(&mut iter, "--outline")?,
+            _ => positional.push(arg.as_str()),
+        }
+    }
+    let [name, output, turns @ ..] = &positional[..] else {
+        return Err(String::from(USAGE));
+    };
+    let mut ds = load_data_storer()?;
+    let mut puzzle = load_puzzle(&mut ds, name)?;
+    let moves = if solve {
+        if !turns.is_empty() {
+            return Err(String::from(
+                "--solve can't be used with a sequence of turns",
+            ));
+        }
+        //undo the whole solve, to get back to the scrambled state
+        let moves = puzzle.stack.clone();
+        while puzzle.undo()? {}
+        moves
+    } else {
+        puzzle.parse_moves(&turns.join(" "))?
+    };
+    let frames = puzzle.animate(&moves, &options, &ColorScheme::default())?;
+    write_bytes_to_file(Path::new(output), &encode_apng(&frames, &options)?)
+        .map_err(|err| format!("error writing {output}: {err}"))
+}
@@ -7,9 +7,13 @@ use crate::hps::custom_values::circle::circle_builtins;
 use crate::hps::custom_values::color::color_builtins;
 use crate::hps::custom_values::hpspuzzle::puzzle_builtins;
 use crate::hps::custom_values::point::point_builtins;
+use crate::hps::custom_values::region::region_builtins;
+use crate::hps::custom_values::sphere::sphere_builtins;
+use crate::hps::custom_values::symmetry::symmetry_builtins;
 use crate::hps::custom_values::turn::turn_builtins;
 use crate::hps::custom_values::vector::vector_builtins;
 use crate::hps::data_storer::data_storer::{PuzzleLoadingData, PuzzlesMap};
+use crate::puzzle::puzzle::{MAX_DIFFICULTY, PuzzleMetadata};
 use hyperpuzzlescript::builtins::*;
 
 pub fn circleguy_hps_builtins(b: &mut Builtins) -> Result<(), FullDiagnostic> {
@@ -30,6 +34,9 @@ pub fn circleguy_builtins(b: &mut Builtins) -> Result<(), FullDiagnostic> {
     turn_builtins(b)?;
     point_builtins(b)?;
     circle_builtins(b)?;
+    region_builtins(b)?;
+    symmetry_builtins(b)?;
+    sphere_builtins(b)?;
     Ok(())
 }
 pub fn loading_builtins(
@@ -39,8 +46,14 @@ pub fn loading_builtins(
 ) -> Result<(), FullDiagnostic> {
     rt.with_builtins(|b| {
         b.set_fns(hps_fns![
-            #[kwargs(name: String, authors: Vec<String>, scramble: usize = 500, (build, span): Arc<FnValue>, experimental: bool = false)]
+            #[kwargs(name: String, authors: Vec<String>, tags: Vec<String> = vec![], scramble: usize = 500, (build, span): Arc<FnValue>, experimental: bool = false, description: String = String::new(), difficulty: usize = 0, created: String = String::new(), inspired_by: Vec<String> = vec![], aliases: Vec<String> = vec![])]
             fn add_puzzle(ctx: EvalCtx) -> () {
+                if difficulty > MAX_DIFFICULTY {
+                    return Err(Error::User(format!("Error: difficulty must be from 1 to {MAX_DIFFICULTY}!").into()).at(ctx.caller_span));
+                }
+                if !created.is_empty() && !is_date(&created) {
+                    return Err(Error::User("Error: created must be a date, as YYYY-MM-DD!".into()).at(ctx.caller_span));
+                }
                 if !experimental || exp {
                     let path = PathBuf::from(ctx.runtime.modules.get_path(ctx.caller_span.context).unwrap().to_string());
                     let mut p = puzzles.lock().unwrap();
@@ -51,6 +64,14 @@ pub fn loading_builtins(
                         PuzzleLoadingData {
                             name,
                             authors,
+                            metadata: PuzzleMetadata {
+                                description,
+                                tags,
+                                difficulty: (difficulty != 0).then_some(difficulty),
+                                created,
+                                inspired_by,
+                                aliases,
+                            },
                             scramble: scramble as usize,
                             constructor: (build, span),
                             path: path.clone(),
@@ -62,3 +83,26 @@ pub fn loading_builtins(
         ])
     })
 }
+
+///whether a string is a date written as YYYY-MM-DD, with a day that exists in its month
+pub fn is_date(date: &str) -> bool {
+    let parts = date.split('-').collect::<Vec<_>>();
+    if parts.len() != 3
+        || ![4, 2, 2]
+            .iter()
+            .zip(&parts)
+            .all(|(len, x)| x.len() == *len && x.chars().all(|c| c.is_ascii_digit()))
+    {
+        return false;
+    }
+    let [year, month, day] = [0, 1, 2].map(|i| parts[i].parse::<u32>().unwrap_or(0));
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days = match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        1..=12 => 31,
+        _ => return false,
+    };
+    (1..=days).contains(&day)
+}
@@ -1,4 +1,4 @@
-use hyperpuzzlescript::{Builtins, CustomValue, FullDiagnostic, TypeOf, hps_fns};
+use hyperpuzzlescript::{Builtins, CustomValue, Error, FullDiagnostic, ListOf, TypeOf, hps_fns};
 
 use crate::complex::{
     c64::C64,
@@ -71,6 +71,68 @@ pub fn circle_builtins(b: &mut Builtins) -> Result<(), FullDiagnostic> {
                 ori: c.ori,
             }
         }
+        fn reflect(ctx: EvalCtx, c: OrientedCircle, a: Point, b: Point) -> OrientedCircle {
+            OrientedCircle {
+                circ: c.circ.reflect_across(a, b).ok_or(
+                    Error::User("Error: a line needs two different points!".into())
+                        .at(ctx.caller_span),
+                )?,
+                ori: c.ori,
+            }
+        }
+        fn intersect(ctx: EvalCtx, a: OrientedCircle, b: OrientedCircle) -> ListOf<Point> {
+            a.circ
+                .intersect_circle(b.circ)
+                .into_iter()
+                .map(|x| (x, ctx.caller_span))
+                .collect()
+        }
+        fn circle_through(ctx: EvalCtx, a: Point, b: Point, c: Point) -> OrientedCircle {
+            OrientedCircle {
+                circ: ComplexCircle::through(a, b, c).ok_or(
+                    Error::User("Error: no circle goes through collinear points!".into())
+                        .at(ctx.caller_span),
+                )?,
+                ori: Contains::Inside,
+            }
+        }
+        fn tangent_circle(ctx: EvalCtx, c: OrientedCircle, center: Point) -> OrientedCircle {
+            OrientedCircle {
+                circ: c.circ.tangent_at(center).ok_or(
+                    Error::User(
+                        "Error: the center of a tangent circle cannot be on the circle!".into(),
+                    )
+                    .at(ctx.caller_span),
+                )?,
+                ori: Contains::Inside,
+            }
+        }
+        fn tangent_circle(
+            ctx: EvalCtx,
+            a: OrientedCircle,
+            b: OrientedCircle,
+            r: f64,
+        ) -> ListOf<OrientedCircle> {
+            if r.is_nan() || r <= 0.0 || r.approx_eq_zero(crate::PRECISION) {
+                return Err(Error::User(
+                    "Error: the radius of a tangent circle has to be positive!".into(),
+                )
+                .at(ctx.caller_span));
+            }
+            a.circ
+                .tangent_circles(b.circ, r)
+                .into_iter()
+                .map(|circ| {
+                    (
+                        OrientedCircle {
+                            circ,
+                            ori: Contains::Inside,
+                        },
+                        ctx.caller_span,
+                    )
+                })
+                .collect()
+        }
     ])?;
     b.set_fns(hps_fns![("~", |_, a: OrientedCircle| -> OrientedCircle {
         -a
@@ -1,6 +1,6 @@
-use hyperpuzzlescript::{Builtins, CustomValue, FullDiagnostic, TypeOf};
+use hyperpuzzlescript::{Builtins, CustomValue, Error, EvalCtx, FullDiagnostic, TypeOf, hps_fns};
 
-use crate::puzzle::color::Color;
+use crate::puzzle::color::{Color, NAMED_COLORS};
 
 impl TypeOf for Color {
     fn hps_ty() -> hyperpuzzlescript::Type {
@@ -16,8 +16,12 @@ impl CustomValue for Color {
         (*self).into()
     }
 
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>, _is_repr: bool) -> std::fmt::Result {
-        write!(f, "test")
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>, is_repr: bool) -> std::fmt::Result {
+        match (self.to_hex(), is_repr) {
+            (Some(hex), true) => write!(f, "hex({hex:?})"),
+            (Some(hex), false) => write!(f, "{hex}"),
+            (None, _) => write!(f, "none"),
+        }
     }
 
     fn eq(&self, other: &hyperpuzzlescript::BoxDynValue) -> Option<bool> {
@@ -26,28 +30,30 @@ impl CustomValue for Color {
 }
 
 pub fn color_builtins(b: &mut Builtins) -> Result<(), FullDiagnostic> {
-    b.set("red", Color::Red)?;
-    b.set("green", Color::Green)?;
-    b.set("blue", Color::Blue)?;
-    b.set("yellow", Color::Yellow)?;
-    b.set("purple", Color::Purple)?;
-    b.set("gray", Color::Gray)?;
-    b.set("black", Color::Black)?;
-    b.set("brown", Color::Brown)?;
-    b.set("cyan", Color::Cyan)?;
-    b.set("white", Color::White)?;
-    b.set("dark_blue", Color::DarkBlue)?;
-    b.set("dark_green", Color::DarkGreen)?;
-    b.set("dark_gray", Color::DarkGray)?;
-    b.set("dark_red", Color::DarkRed)?;
-    b.set("light_blue", Color::LightBlue)?;
-    b.set("light_gray", Color::LightGray)?;
-    b.set("light_green", Color::LightGreen)?;
-    b.set("light_red", Color::LightRed)?;
-    b.set("light_yellow", Color::LightYellow)?;
-    b.set("khaki", Color::Khaki)?;
-    b.set("gold", Color::Gold)?;
-    b.set("magenta", Color::Magenta)?;
-    b.set("orange", Color::Orange)?;
+    for (name, color) in NAMED_COLORS {
+        b.set(name, color)?;
+    }
+    b.set_fns(hps_fns![
+        fn rgb(ctx: EvalCtx, r: f64, g: f64, b: f64) -> Color {
+            let mut channels = [0; 3];
+            for (channel, value) in channels.iter_mut().zip([r, g, b]) {
+                if !(0.0..=255.0).contains(&value) || value.fract() != 0.0 {
+                    return Err(Error::User(
+                        format!("Color values must be whole numbers from 0 to 255, not {value}.")
+                            .into(),
+                    )
+                    .at(ctx.caller_span));
+                }
+                *channel = value as u8;
+            }
+            Color::rgb(channels[0], channels[1], channels[2])
+        }
+        fn hex(ctx: EvalCtx, hex: String) -> Color {
+            Color::from_hex(&hex).ok_or(
+                Error::User(format!("`{hex}` is not a hex color like \"#rrggbb\".").into())
+                    .at(ctx.caller_span),
+            )?
+        }
+    ])?;
     Ok(())
 }
@@ -1,16 +1,28 @@
 use std::{
+    f64::consts::PI,
     sync::{Arc, Mutex},
     usize,
 };
 
-use hyperpuzzlescript::{Builtins, CustomValue, Error, EvalCtx, FullDiagnostic, TypeOf, hps_fns};
+use hyperpuzzlescript::{
+    Builtins, CustomValue, Error, EvalCtx, FullDiagnostic, ListOf, TypeOf, hps_fns,
+};
 
 use crate::{
-    complex::complex_circle::OrientedCircle,
-    hps::custom_values::hpspuzzledata::HPSPuzzleData,
-    puzzle::{color::Color, turn::OrderedTurn},
+    complex::{
+        c64::C64,
+        complex_circle::OrientedCircle,
+        point::Point,
+        sphere::SpherePoint,
+        symmetry::{Isometry, Symmetry},
+    },
+    hps::custom_values::{hpspuzzledata::HPSPuzzleData, symmetry::mirror_line},
+    puzzle::{color::Color, region::Region, turn::OrderedTurn},
 };
 
+///the error given by the symmetric commands when the definition hasn't declared a symmetry
+const NO_SYMMETRY: &str = "No symmetry declared. Call `set_symmetry` first.";
+
 #[derive(Clone, Debug)]
 pub struct HPSPuzzle(pub Arc<Mutex<HPSPuzzleData>>);
 
@@ -85,6 +97,12 @@ pub fn puzzle_builtins(b: &mut Builtins) -> Result<(), FullDiagnostic> {
                 .or(Err(Error::Internal("Internal error when cutting!").at(s)))?;
         }
         fn cut(ctx: EvalCtx, region: Vec<OrientedCircle>, cut: Vec<OrderedTurn>) -> () {
+            let s = ctx.caller_span;
+            puzzle(ctx)
+                .cut_region(&region.into(), &cut)
+                .or(Err(Error::Internal("Internal error when cutting!").at(s)))?;
+        }
+        fn cut(ctx: EvalCtx, region: Region, cut: Vec<OrderedTurn>) -> () {
             let s = ctx.caller_span;
             puzzle(ctx)
                 .cut_region(&region, &cut)
@@ -123,8 +141,148 @@ pub fn puzzle_builtins(b: &mut Builtins) -> Result<(), FullDiagnostic> {
                 .or(Err(Error::Internal("Internal error when undoing!").at(s)))?;
         }
         fn color(ctx: EvalCtx, region: Vec<OrientedCircle>, color: Color) -> () {
+            puzzle(ctx).color(&region.into(), color);
+        }
+        fn color(ctx: EvalCtx, region: Region, color: Color) -> () {
             puzzle(ctx).color(&region, color);
         }
+        fn color(ctx: EvalCtx, region: Vec<OrientedCircle>, name: String) -> () {
+            let s = ctx.caller_span;
+            let mut p = puzzle(ctx);
+            let color = p.palette_color(&name).ok_or(
+                Error::User(format!("No color named `{name}` in the palette.").into()).at(s),
+            )?;
+            p.color(&region.into(), color);
+        }
+        fn color(ctx: EvalCtx, region: Region, name: String) -> () {
+            let s = ctx.caller_span;
+            let mut p = puzzle(ctx);
+            let color = p.palette_color(&name).ok_or(
+                Error::User(format!("No color named `{name}` in the palette.").into()).at(s),
+            )?;
+            p.color(&region, color);
+        }
+        fn mirror(ctx: EvalCtx) -> () {
+            //mirror left to right, across the y axis
+            let axis = Isometry::Reflection {
+                center: Point(C64 { re: 0.0, im: 0.0 }),
+                angle: PI / 2.0,
+            };
+            puzzle(ctx).transform(&axis);
+        }
+        fn mirror(ctx: EvalCtx, a: Point, b: Point) -> () {
+            let axis = mirror_line(a, b, ctx.caller_span)?;
+            puzzle(ctx).transform(&axis);
+        }
+        fn set_spherical(ctx: EvalCtx) -> () {
+            puzzle(ctx).spherical = true;
+        }
+        fn set_spherical(ctx: EvalCtx, x: f64, y: f64, z: f64) -> () {
+            let s = ctx.caller_span;
+            let pole = SpherePoint::from_direction(x, y, z)
+                .ok_or(Error::User("Error: a pole can't be the zero vector!".into()).at(s))?;
+            let mut p = puzzle(ctx);
+            p.spherical = true;
+            p.pole = pole;
+        }
+        fn add_sphere(ctx: EvalCtx) -> () {
+            let s = ctx.caller_span;
+            if !puzzle(ctx).add_sphere() {
+                return Err(Error::User(
+                    "Error: the sphere has to be added before any circles!".into(),
+                )
+                .at(s));
+            }
+        }
+        fn set_symmetry(ctx: EvalCtx, symmetry: Symmetry) -> () {
+            puzzle(ctx).symmetry = Some(symmetry);
+        }
+        fn symmetric_cut(ctx: EvalCtx, cut: Vec<OrderedTurn>) -> () {
+            let s = ctx.caller_span;
+            let mut p = puzzle(ctx);
+            let symmetry = p.symmetry.ok_or(Error::User(NO_SYMMETRY.into()).at(s))?;
+            p.symmetric_cut(&symmetry, &Region::everywhere(), &cut)
+                .or(Err(Error::Internal("Internal error when cutting!").at(s)))?;
+        }
+        fn symmetric_cut(ctx: EvalCtx, region: Region, cut: Vec<OrderedTurn>) -> () {
+            let s = ctx.caller_span;
+            let mut p = puzzle(ctx);
+            let symmetry = p.symmetry.ok_or(Error::User(NO_SYMMETRY.into()).at(s))?;
+            p.symmetric_cut(&symmetry, &region, &cut)
+                .or(Err(Error::Internal("Internal error when cutting!").at(s)))?;
+        }
+        fn symmetric_cut(ctx: EvalCtx, region: Vec<OrientedCircle>, cut: Vec<OrderedTurn>) -> () {
+            let s = ctx.caller_span;
+            let mut p = puzzle(ctx);
+            let symmetry = p.symmetry.ok_or(Error::User(NO_SYMMETRY.into()).at(s))?;
+            p.symmetric_cut(&symmetry, &region.into(), &cut)
+                .or(Err(Error::Internal("Internal error when cutting!").at(s)))?;
+        }
+        fn symmetric_color(ctx: EvalCtx, region: Region, color: Color) -> () {
+            let s = ctx.caller_span;
+            let mut p = puzzle(ctx);
+            let symmetry = p.symmetry.ok_or(Error::User(NO_SYMMETRY.into()).at(s))?;
+            p.symmetric_color(&symmetry, &region, color);
+        }
+        fn symmetric_color(ctx: EvalCtx, region: Vec<OrientedCircle>, color: Color) -> () {
+            let s = ctx.caller_span;
+            let mut p = puzzle(ctx);
+            let symmetry = p.symmetry.ok_or(Error::User(NO_SYMMETRY.into()).at(s))?;
+            p.symmetric_color(&symmetry, &region.into(), color);
+        }
+        fn symmetric_color(ctx: EvalCtx, region: Region, name: String) -> () {
+            let s = ctx.caller_span;
+            let mut p = puzzle(ctx);
+            let symmetry = p.symmetry.ok_or(Error::User(NO_SYMMETRY.into()).at(s))?;
+            let color = p.palette_color(&name).ok_or(
+                Error::User(format!("No color named `{name}` in the palette.").into()).at(s),
+            )?;
+            p.symmetric_color(&symmetry, &region, color);
+        }
+        fn symmetric_color(ctx: EvalCtx, region: Vec<OrientedCircle>, name: String) -> () {
+            let s = ctx.caller_span;
+            let mut p = puzzle(ctx);
+            let symmetry = p.symmetry.ok_or(Error::User(NO_SYMMETRY.into()).at(s))?;
+            let color = p.palette_color(&name).ok_or(
+                Error::User(format!("No color named `{name}` in the palette.").into()).at(s),
+            )?;
+            p.symmetric_color(&symmetry, &region.into(), color);
+        }
+        fn orbit(ctx: EvalCtx, circle: OrientedCircle) -> ListOf<OrientedCircle> {
+            let s = ctx.caller_span;
+            let symmetry = puzzle(ctx)
+                .symmetry
+                .ok_or(Error::User(NO_SYMMETRY.into()).at(s))?;
+            symmetry
+                .orbit_circle(circle)
+                .into_iter()
+                .map(|x| (x, s))
+                .collect()
+        }
+        fn orbit(ctx: EvalCtx, point: Point) -> ListOf<Point> {
+            let s = ctx.caller_span;
+            let symmetry = puzzle(ctx)
+                .symmetry
+                .ok_or(Error::User(NO_SYMMETRY.into()).at(s))?;
+            symmetry
+                .orbit_point(point)
+                .into_iter()
+                .map(|x| (x, s))
+                .collect()
+        }
+        fn add_color(ctx: EvalCtx, color: Color, name: String) -> () {
+            puzzle(ctx).add_color(name, color);
+        }
+        fn add_colors(ctx: EvalCtx, colors: Vec<Color>, names: Vec<String>) -> () {
+            let s = ctx.caller_span;
+            let mut p = puzzle(ctx);
+            if colors.len() != names.len() {
+                return Err(Error::User("Inequal number of colors and names passed.".into()).at(s));
+            }
+            for (color, name) in colors.into_iter().zip(names) {
+                p.add_color(name, color);
+            }
+        }
     ])
 }
 
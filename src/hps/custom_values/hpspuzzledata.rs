@@ -7,9 +7,16 @@ use crate::{
     complex::{
         arc::Arc,
         complex_circle::{ComplexCircle, Contains, OrientedCircle},
+        sphere::SpherePoint,
+        symmetry::{Isometry, Symmetry},
     },
     puzzle::{
-        color::Color, piece::Piece, piece_shape::PieceShape, puzzle::PuzzleData, turn::OrderedTurn,
+        color::Color,
+        piece::Piece,
+        piece_shape::PieceShape,
+        puzzle::{PuzzleData, PuzzleMetadata},
+        region::Region,
+        turn::OrderedTurn,
     },
 };
 
@@ -23,6 +30,10 @@ pub struct HPSPuzzleData {
     pub intern: FloatPool,
     pub disks: Vec<ComplexCircle>,
     pub scramble: usize,
+    pub palette: Vec<(String, Color)>, //the named colors declared by the definition, in the order they were added
+    pub symmetry: Option<Symmetry>, //the symmetry declared by the definition, used by the symmetric commands
+    pub spherical: bool, //whether the definition declared the puzzle to be the stereographic projection of a sphere
+    pub pole: SpherePoint, //the point of the sphere the puzzle is projected from, which is at infinity
 }
 
 impl HPSPuzzleData {
@@ -63,6 +74,10 @@ impl HPSPuzzleData {
             intern: FloatPool::new(PRECISION),
             disks: vec![],
             scramble: 0,
+            palette: vec![],
+            symmetry: None,
+            spherical: false,
+            pole: SpherePoint::SOUTH_POLE,
         }
     }
     pub fn to_puzzle_data(&self, path: &PathBuf) -> PuzzleData {
@@ -73,11 +88,28 @@ impl HPSPuzzleData {
             turns: self.turns.clone(),
             intern: self.intern.clone(),
             depth: self.scramble,
+            palette: self.palette.clone(),
+            spherical: self.spherical,
+            metadata: PuzzleMetadata::default(),
             keybinds: HashMap::new(),
             path: path.clone(),
         }
     }
     pub fn add_disk(&mut self, disk: ComplexCircle) -> bool {
+        if self.pieces.iter().any(|x| x.shape.contains_infinity()) {
+            //the whole sphere is already there, so the disk only cuts it
+            self.pieces = self
+                .pieces
+                .iter()
+                .flat_map(|x| match x.cut_by_circle(disk) {
+                    Some((i, o)) => vec![i, o],
+                    None => vec![x.clone()],
+                })
+                .collect();
+            self.disks.push(disk);
+            self.intern_all();
+            return true;
+        }
         let mut disk_piece = full_circle_piece(disk);
         for old_disk in &self.disks {
             if let Some((_, o)) = disk_piece.cut_by_circle(*old_disk) {
@@ -91,6 +123,21 @@ impl HPSPuzzleData {
         self.intern_all();
         true
     }
+    ///add the whole sphere (the whole plane, along with infinity) as a single piece, for spherical puzzles. returns
+    ///false if circles were already added, since the sphere would overlap them
+    pub fn add_sphere(&mut self) -> bool {
+        if !self.pieces.is_empty() {
+            return false;
+        }
+        self.pieces.push(Piece {
+            shape: PieceShape {
+                bounds: Vec::new(),
+                border: Vec::new(),
+            },
+            color: Color::None,
+        });
+        true
+    }
     pub fn turn(&mut self, turn: OrderedTurn, cut: bool) -> Result<bool, String> {
         let mut new_pieces = Vec::new(); //make a list of new pieces to populate
         if cut {
@@ -121,15 +168,11 @@ impl HPSPuzzleData {
         self.undo_num(cut.len())?;
         Ok(())
     }
-    pub fn cut_region(
-        &mut self,
-        region: &Vec<OrientedCircle>,
-        cut: &Vec<OrderedTurn>,
-    ) -> Result<(), String> {
+    pub fn cut_region(&mut self, region: &Region, cut: &Vec<OrderedTurn>) -> Result<(), String> {
         let mut in_pieces = vec![];
         let mut out_pieces = vec![];
         for piece in &self.pieces {
-            let in_r = piece.in_region(region);
+            let in_r = region.contains_piece(piece);
             if in_r {
                 in_pieces.push(piece.clone());
             } else {
@@ -141,6 +184,36 @@ impl HPSPuzzleData {
         self.pieces.extend(out_pieces);
         Ok(())
     }
+    ///cut the pieces in the region by the turns, then do the same with the region and turns moved by every other
+    ///element of the symmetry
+    pub fn symmetric_cut(
+        &mut self,
+        symmetry: &Symmetry,
+        region: &Region,
+        cut: &[OrderedTurn],
+    ) -> Result<(), String> {
+        for element in symmetry.elements() {
+            let turns = cut.iter().map(|x| x.transformed(&element)).collect();
+            self.cut_region(&region.transformed(&element), &turns)?;
+        }
+        Ok(())
+    }
+    ///move the whole puzzle by an isometry: its pieces, circles, turns, the turns that haven't been undone and its
+    ///symmetry. a reflection gives the puzzle's mirror image, whose turns go the other way
+    pub fn transform(&mut self, isometry: &Isometry) {
+        self.pieces = self
+            .pieces
+            .iter()
+            .map(|x| x.transformed(isometry))
+            .collect();
+        self.disks = self.disks.iter().map(|x| isometry.circle(*x)).collect();
+        for turn in self.turns.values_mut() {
+            *turn = turn.transformed(isometry);
+        }
+        self.stack = self.stack.iter().map(|x| x.transformed(isometry)).collect();
+        self.symmetry = self.symmetry.map(|x| x.transformed(isometry));
+        self.intern_all();
+    }
     ///returns true if something was there to be undone
     pub fn undo(&mut self) -> Result<bool, String> {
         if let Some(t) = self.stack.pop() {
@@ -161,13 +234,33 @@ impl HPSPuzzleData {
         while self.undo()? {}
         Ok(())
     }
-    pub fn color(&mut self, region: &Vec<OrientedCircle>, color: Color) {
+    ///add a named color to the palette, replacing the color if the name is already used
+    pub fn add_color(&mut self, name: String, color: Color) {
+        match self.palette.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, c)) => *c = color,
+            None => self.palette.push((name, color)),
+        }
+    }
+    ///get a named color from the palette
+    pub fn palette_color(&self, name: &str) -> Option<Color> {
+        self.palette
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, c)| *c)
+    }
+    pub fn color(&mut self, region: &Region, color: Color) {
         for piece in &mut self.pieces {
-            if piece.in_region(region) {
+            if region.contains_piece(piece) {
                 piece.color = color;
             }
         }
     }
+    ///color the region, and its image under every element of the symmetry
+    pub fn symmetric_color(&mut self, symmetry: &Symmetry, region: &Region, color: Color) {
+        for element in symmetry.elements() {
+            self.color(&region.transformed(&element), color);
+        }
+    }
     // pub fn next_turn_name(&self) -> Option<String> {
     //     for ch in NAMES {
     //         if !self.turns.contains_key(ch) {
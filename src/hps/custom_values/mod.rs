@@ -3,5 +3,8 @@ pub mod color;
 pub mod hpspuzzle;
 pub mod hpspuzzledata;
 pub mod point;
+pub mod region;
+pub mod sphere;
+pub mod symmetry;
 pub mod turn;
 pub mod vector;
@@ -1,4 +1,4 @@
-use hyperpuzzlescript::{Builtins, CustomValue, FullDiagnostic, TypeOf, hps_fns};
+use hyperpuzzlescript::{Builtins, CustomValue, Error, FullDiagnostic, TypeOf, hps_fns};
 
 use crate::complex::{c64::C64, point::Point, vector::Vector};
 use approx_collections::ApproxEq;
@@ -50,6 +50,17 @@ pub fn point_builtins(b: &mut Builtins) -> Result<(), FullDiagnostic> {
         fn rotate(point: Point, cent: Point, angle: f64) -> Point {
             point.rotate_about(cent, angle)
         }
+        fn dist(a: Point, b: Point) -> f64 {
+            a.dist(b)
+        }
+        fn midpoint(a: Point, b: Point) -> Point {
+            a.midpoint(b)
+        }
+        fn reflect(ctx: EvalCtx, point: Point, a: Point, b: Point) -> Point {
+            point.reflect_across(a, b).ok_or(
+                Error::User("Error: a line needs two different points!".into()).at(ctx.caller_span),
+            )?
+        }
     ])?;
     b.set_fns(hps_fns![
         ("+", |_, a: Point, b: Vector| -> Point { a + b }),
@@ -0,0 +1,92 @@
+use hyperpuzzlescript::{Builtins, CustomValue, FullDiagnostic, TypeOf, hps_fns};
+
+use crate::{
+    complex::{complex_circle::OrientedCircle, point::Point},
+    puzzle::region::Region,
+};
+
+impl TypeOf for Region {
+    fn hps_ty() -> hyperpuzzlescript::Type {
+        hyperpuzzlescript::Type::Custom("Region")
+    }
+}
+impl CustomValue for Region {
+    fn type_name(&self) -> &'static str {
+        "Region"
+    }
+
+    fn clone_dyn(&self) -> hyperpuzzlescript::BoxDynValue {
+        self.clone().into()
+    }
+
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>, is_repr: bool) -> std::fmt::Result {
+        if self.is_everywhere() {
+            write!(f, "everywhere")
+        } else if is_repr {
+            write!(f, "Region({} parts)", self.0.len())
+        } else {
+            write!(f, "region of {} parts", self.0.len())
+        }
+    }
+
+    fn eq(&self, _other: &hyperpuzzlescript::BoxDynValue) -> Option<bool> {
+        None
+    }
+}
+
+pub fn region_builtins(b: &mut Builtins) -> Result<(), FullDiagnostic> {
+    b.set("everywhere", Region::everywhere())?;
+    b.set("nowhere", Region::nowhere())?;
+    b.set_fns(hps_fns![
+        fn region(circle: OrientedCircle) -> Region {
+            Region::from(circle)
+        }
+        fn region(circles: Vec<OrientedCircle>) -> Region {
+            Region::from(circles)
+        }
+        fn complement(region: Region) -> Region {
+            region.complement()
+        }
+        fn contains(region: Region, point: Point) -> bool {
+            region.contains(point)
+        }
+    ])?;
+    b.set_fns(hps_fns![
+        ("&", |_, a: Region, b: Region| -> Region {
+            a.intersection(&b)
+        }),
+        ("&", |_, a: Region, b: OrientedCircle| -> Region {
+            a.intersection(&b.into())
+        }),
+        ("&", |_, a: OrientedCircle, b: Region| -> Region {
+            Region::from(a).intersection(&b)
+        }),
+        ("&", |_, a: OrientedCircle, b: OrientedCircle| -> Region {
+            Region::from(vec![a, b])
+        }),
+        ("|", |_, a: Region, b: Region| -> Region { a.union(&b) }),
+        ("|", |_, a: Region, b: OrientedCircle| -> Region {
+            a.union(&b.into())
+        }),
+        ("|", |_, a: OrientedCircle, b: Region| -> Region {
+            Region::from(a).union(&b)
+        }),
+        ("|", |_, a: OrientedCircle, b: OrientedCircle| -> Region {
+            Region::from(a).union(&b.into())
+        }),
+        ("-", |_, a: Region, b: Region| -> Region {
+            a.difference(&b)
+        }),
+        ("-", |_, a: Region, b: OrientedCircle| -> Region {
+            a.difference(&b.into())
+        }),
+        ("-", |_, a: OrientedCircle, b: Region| -> Region {
+            Region::from(a).difference(&b)
+        }),
+        ("-", |_, a: OrientedCircle, b: OrientedCircle| -> Region {
+            Region::from(vec![a, -b])
+        }),
+        ("~", |_, a: Region| -> Region { a.complement() })
+    ])?;
+    b.set_custom_ty::<Region>()
+}
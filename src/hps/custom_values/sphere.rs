@@ -0,0 +1,64 @@
+use hyperpuzzlescript::{Builtins, Error, EvalCtx, FullDiagnostic, hps_fns};
+
+use crate::{
+    complex::{
+        complex_circle::OrientedCircle,
+        point::Point,
+        sphere::{self, SpherePoint},
+    },
+    hps::custom_values::{hpspuzzle::HPSPuzzle, turn::order_rotation},
+    puzzle::turn::{OrderedTurn, Turn},
+};
+
+pub fn sphere_builtins(b: &mut Builtins) -> Result<(), FullDiagnostic> {
+    b.set_fns(hps_fns![
+        fn sphere_point(ctx: EvalCtx, x: f64, y: f64, z: f64) -> Point {
+            let s = ctx.caller_span;
+            axis(ctx, x, y, z)?.to_point().ok_or(
+                Error::User("Error: the pole the puzzle is projected from can't be projected!".into())
+                    .at(s),
+            )?
+        }
+        fn cap(ctx: EvalCtx, x: f64, y: f64, z: f64, radius: f64) -> OrientedCircle {
+            let s = ctx.caller_span;
+            sphere::cap(axis(ctx, x, y, z)?, radius).ok_or(
+                Error::User(
+                    "Error: the edge of a cap can't go through the pole the puzzle is projected from!"
+                        .into(),
+                )
+                .at(s),
+            )?
+        }
+        fn sphere_turn(ctx: EvalCtx, x: f64, y: f64, z: f64, radius: f64, num: i64) -> OrderedTurn {
+            let s = ctx.caller_span;
+            OrderedTurn {
+                turn: Turn::spherical(axis(ctx, x, y, z)?, radius, order_rotation(num)).ok_or(
+                    Error::User(
+                        "Error: the edge of a turned cap can't go through the pole the puzzle is projected from!"
+                            .into(),
+                    )
+                    .at(s),
+                )?,
+                order: num as usize,
+            }
+        }
+    ])
+}
+
+///the point of the sphere in a direction, turned so that the pole the puzzle is projected from (see set_spherical) is
+///the south pole, or an error for the zero vector
+fn axis(ctx: &mut EvalCtx, x: f64, y: f64, z: f64) -> hyperpuzzlescript::Result<SpherePoint> {
+    let point = SpherePoint::from_direction(x, y, z).ok_or(
+        Error::User("Error: an axis can't be the zero vector!".into()).at(ctx.caller_span),
+    )?;
+    Ok(point.from_pole(pole(ctx)))
+}
+
+///the pole the puzzle being built is projected from, or the south pole outside of a puzzle
+fn pole(ctx: &mut EvalCtx) -> SpherePoint {
+    ctx.scope
+        .special
+        .puz
+        .as_ref::<HPSPuzzle>()
+        .map_or(SpherePoint::SOUTH_POLE, |x| x.0.lock().unwrap().pole)
+}
@@ -0,0 +1,122 @@
+use hyperpuzzlescript::{
+    Builtins, CustomValue, Error, FullDiagnostic, ListOf, Span, TypeOf, hps_fns,
+};
+
+use crate::{
+    complex::{
+        complex_circle::OrientedCircle,
+        point::Point,
+        symmetry::{Isometry, Symmetry},
+    },
+    puzzle::{region::Region, turn::OrderedTurn},
+};
+
+impl TypeOf for Symmetry {
+    fn hps_ty() -> hyperpuzzlescript::Type {
+        hyperpuzzlescript::Type::Custom("Symmetry")
+    }
+}
+impl CustomValue for Symmetry {
+    fn type_name(&self) -> &'static str {
+        "Symmetry"
+    }
+
+    fn clone_dyn(&self) -> hyperpuzzlescript::BoxDynValue {
+        (*self).into()
+    }
+
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>, is_repr: bool) -> std::fmt::Result {
+        let kind = if self.dihedral { "dihedral" } else { "cyclic" };
+        let center = self.center.0;
+        if is_repr {
+            write!(
+                f,
+                "{kind}(Point({}, {}), {})",
+                center.re, center.im, self.order
+            )
+        } else {
+            write!(f, "{kind} symmetry of order {}", self.order)
+        }
+    }
+
+    fn eq(&self, _other: &hyperpuzzlescript::BoxDynValue) -> Option<bool> {
+        None
+    }
+    fn field_get(
+        &self,
+        _self_span: hyperpuzzlescript::Span,
+        (field, _field_span): hyperpuzzlescript::Spanned<&str>,
+    ) -> hyperpuzzlescript::Result<Option<hyperpuzzlescript::ValueData>> {
+        Ok(match field {
+            "c" | "cent" | "center" => Some(self.center.into()),
+            "order" | "ord" | "num" => Some(self.order.into()),
+            _ => None,
+        })
+    }
+}
+
+pub fn symmetry_builtins(b: &mut Builtins) -> Result<(), FullDiagnostic> {
+    b.set_fns(hps_fns![
+        fn cyclic(center: Point, order: usize) -> Symmetry {
+            Symmetry {
+                center,
+                order,
+                dihedral: false,
+                axis: 0.0,
+            }
+        }
+        fn dihedral(center: Point, order: usize) -> Symmetry {
+            Symmetry {
+                center,
+                order,
+                dihedral: true,
+                axis: 0.0,
+            }
+        }
+        fn dihedral(center: Point, order: usize, axis: f64) -> Symmetry {
+            Symmetry {
+                center,
+                order,
+                dihedral: true,
+                axis,
+            }
+        }
+        fn orbit(
+            ctx: EvalCtx,
+            symmetry: Symmetry,
+            circle: OrientedCircle,
+        ) -> ListOf<OrientedCircle> {
+            symmetry
+                .orbit_circle(circle)
+                .into_iter()
+                .map(|x| (x, ctx.caller_span))
+                .collect()
+        }
+        fn orbit(ctx: EvalCtx, symmetry: Symmetry, point: Point) -> ListOf<Point> {
+            symmetry
+                .orbit_point(point)
+                .into_iter()
+                .map(|x| (x, ctx.caller_span))
+                .collect()
+        }
+        fn mirror(ctx: EvalCtx, point: Point, a: Point, b: Point) -> Point {
+            mirror_line(a, b, ctx.caller_span)?.point(point)
+        }
+        fn mirror(ctx: EvalCtx, circle: OrientedCircle, a: Point, b: Point) -> OrientedCircle {
+            mirror_line(a, b, ctx.caller_span)?.oriented_circle(circle)
+        }
+        fn mirror(ctx: EvalCtx, turn: OrderedTurn, a: Point, b: Point) -> OrderedTurn {
+            turn.transformed(&mirror_line(a, b, ctx.caller_span)?)
+        }
+        fn mirror(ctx: EvalCtx, region: Region, a: Point, b: Point) -> Region {
+            region.transformed(&mirror_line(a, b, ctx.caller_span)?)
+        }
+    ])?;
+    b.set_custom_ty::<Symmetry>()
+}
+
+///the reflection across the line through two points, or an error at the span if they are the same point
+pub fn mirror_line(a: Point, b: Point, span: Span) -> hyperpuzzlescript::Result<Isometry> {
+    Isometry::reflection_through(a, b)
+        .ok_or(Error::User("Error: a line needs two different points!".into()).at(span))
+}
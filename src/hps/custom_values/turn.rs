@@ -1,10 +1,11 @@
 use std::f64::consts::PI;
 
-use hyperpuzzlescript::{Builtins, CustomValue, FullDiagnostic, ListOf, TypeOf, hps_fns};
+use hyperpuzzlescript::{Builtins, CustomValue, Error, FullDiagnostic, ListOf, TypeOf, hps_fns};
 
 use crate::{
     complex::{
         complex_circle::{Contains, OrientedCircle},
+        point::Point,
         rotation::Rotation,
     },
     puzzle::turn::{OrderedTurn, Turn},
@@ -49,6 +50,7 @@ impl CustomValue for OrderedTurn {
                 .into(),
             ),
             "order" | "ord" | "num" => Some(self.order.into()),
+            "pivot" => Some(self.turn.center().into()),
             _ => None,
         })
     }
@@ -60,11 +62,24 @@ pub fn turn_builtins(b: &mut Builtins) -> Result<(), FullDiagnostic> {
             OrderedTurn {
                 turn: Turn {
                     circle: c.circ,
-                    rot: Rotation::from_angle(if num == 0 {
-                        0.0
-                    } else {
-                        (-2.0 * PI) / (num as f64)
-                    }),
+                    rot: order_rotation(num),
+                    pivot: None,
+                },
+                order: num as usize,
+            }
+        }
+        fn turn(ctx: EvalCtx, c: OrientedCircle, num: i64, pivot: Point) -> OrderedTurn {
+            if c.circ.contains(pivot) != Contains::Inside {
+                return Err(Error::User(
+                    "Error: the pivot of a turn must be inside its circle!".into(),
+                )
+                .at(ctx.caller_span));
+            }
+            OrderedTurn {
+                turn: Turn {
+                    circle: c.circ,
+                    rot: order_rotation(num),
+                    pivot: Some(pivot),
                 },
                 order: num as usize,
             }
@@ -98,6 +113,15 @@ pub fn turn_builtins(b: &mut Builtins) -> Result<(), FullDiagnostic> {
     b.set_custom_ty::<OrderedTurn>()
 }
 
+///the rotation of a turn of an order, clockwise by 2pi/order (or no rotation for order 0)
+pub fn order_rotation(num: i64) -> Rotation {
+    Rotation::from_angle(if num == 0 {
+        0.0
+    } else {
+        (-2.0 * PI) / (num as f64)
+    })
+}
+
 fn flip_turn_seq(turn_seq: Vec<OrderedTurn>) -> Vec<OrderedTurn> {
     turn_seq.iter().rev().map(|x| x.inverse()).collect()
 }
@@ -50,6 +50,9 @@ pub fn vector_builtins(b: &mut Builtins) -> Result<(), FullDiagnostic> {
         fn mag(v: Vector) -> f64 {
             v.mag()
         }
+        fn angle(v: Vector) -> f64 {
+            v.angle()
+        }
         fn normalize(ctx: EvalCtx, v: Vector) -> Vector {
             v.normalize().ok_or(
                 Error::User("Error: zero vector cannot be normalized!".into()).at(ctx.caller_span),
@@ -0,0 +1,327 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::HashMap,
+};
+
+use approx_collections::ApproxEq;
+
+use crate::{
+    PRECISION,
+    complex::complex_circle::Circle,
+    hps::data_storer::data_storer::{DataStorer, PuzzleLoadingData},
+};
+
+///how many recently played puzzles are remembered
+pub const RECENT_COUNT: usize = 10;
+///how often the app checks for the counts found by the background thread while it is counting
+pub const COUNT_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///the turns and pieces of a puzzle, which are only known once it has been built
+pub struct PuzzleCounts {
+    pub turns: usize,   //the number of named turns
+    pub circles: usize, //the number of different circles the turns turn
+    pub pieces: usize,  //the number of pieces in the solved state
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+///the order the puzzles found by the catalog are listed in
+pub enum CatalogSort {
+    #[default]
+    Relevance, //best search match first, then by name
+    Name,
+    Newest,     //most recently created first
+    Difficulty, //easiest first
+    Pieces,     //fewest pieces first
+    Turns,      //fewest turns first
+}
+
+impl CatalogSort {
+    pub const ALL: [Self; 6] = [
+        Self::Relevance,
+        Self::Name,
+        Self::Newest,
+        Self::Difficulty,
+        Self::Pieces,
+        Self::Turns,
+    ];
+    ///the name of the order, as shown in the sorting menu
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Relevance => "Relevance",
+            Self::Name => "Name",
+            Self::Newest => "Newest",
+            Self::Difficulty => "Difficulty",
+            Self::Pieces => "Pieces",
+            Self::Turns => "Turns",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+///the search, filters and sorting of the puzzle list, and the puzzles the user has starred or played
+pub struct Catalog {
+    pub search: String,         //fuzzy matched against names, aliases and authors
+    pub author: Option<String>, //only show puzzles by this author
+    pub tag: Option<String>,    //only show puzzles with this tag
+    pub turns: Option<(usize, usize)>, //only show puzzles with a number of turns in this range
+    pub pieces: Option<(usize, usize)>, //only show puzzles with a number of pieces in this range
+    pub sort: CatalogSort,      //the order the results are listed in
+    pub favorites: Vec<String>, //the starred puzzles, by PuzzleLoadingData::key
+    pub recent: Vec<String>, //the last puzzles played, most recent first, by PuzzleLoadingData::key
+    pub selected: usize,     //the index of the result picked with the arrow keys
+}
+
+///how well a query fuzzy matches a text, ignoring case. every character of the query has to appear in the text in
+///order. characters that follow the last match or start a word score higher. None if the text doesn't match
+pub fn fuzzy_score(query: &str, text: &str) -> Option<usize> {
+    let text = text.to_lowercase().chars().collect::<Vec<char>>();
+    let mut score = 0;
+    let mut next = 0; //the index of the text to keep searching from
+    for c in query.to_lowercase().chars().filter(|x| !x.is_whitespace()) {
+        let found = next + text[next..].iter().position(|x| *x == c)?;
+        score += 1;
+        if found > 0 && found == next {
+            score += 2;
+        }
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 2;
+        }
+        next = found + 1;
+    }
+    Some(score)
+}
+
+impl Catalog {
+    ///whether the list should show the matching puzzles rather than the folders
+    pub fn is_filtering(&self) -> bool {
+        !self.search.trim().is_empty()
+            || self.author.is_some()
+            || self.tag.is_some()
+            || self.turns.is_some()
+            || self.pieces.is_some()
+            || self.sort != CatalogSort::default()
+    }
+    ///whether the turns and pieces of the puzzles have to be counted to filter or sort them
+    pub fn needs_counts(&self) -> bool {
+        self.turns.is_some()
+            || self.pieces.is_some()
+            || matches!(self.sort, CatalogSort::Pieces | CatalogSort::Turns)
+    }
+    ///how well a puzzle matches the search, or None if it is filtered out. puzzles that haven't been counted yet are
+    ///kept, and puzzles that failed to build are left out when filtering by their counts
+    pub fn score(
+        &self,
+        data: &PuzzleLoadingData,
+        counts: Option<&Option<PuzzleCounts>>,
+    ) -> Option<usize> {
+        if let Some(author) = &self.author
+            && !data.authors.contains(author)
+        {
+            return None;
+        }
+        if let Some(tag) = &self.tag
+            && !data.metadata.tags.contains(tag)
+        {
+            return None;
+        }
+        let in_range =
+            |range: Option<(usize, usize)>, count: fn(&PuzzleCounts) -> usize| match range {
+                None => true,
+                Some((min, max)) => match counts {
+                    None => true,
+                    Some(None) => false,
+                    Some(Some(x)) => (min..=max).contains(&count(x)),
+                },
+            };
+        if !in_range(self.turns, |x| x.turns) || !in_range(self.pieces, |x| x.pieces) {
+            return None;
+        }
+        std::iter::once(&data.name)
+            .chain(&data.metadata.aliases)
+            .chain(&data.authors)
+            .filter_map(|x| fuzzy_score(&self.search, x))
+            .max()
+    }
+    ///the puzzles that pass the search and filters, in the chosen order
+    pub fn results(
+        &self,
+        defs: Vec<PuzzleLoadingData>,
+        counts: &HashMap<String, Option<PuzzleCounts>>,
+    ) -> Vec<PuzzleLoadingData> {
+        let mut results = defs
+            .into_iter()
+            .filter_map(|x| {
+                let count = counts.get(&x.key());
+                Some((self.score(&x, count)?, count.copied().flatten(), x))
+            })
+            .collect::<Vec<_>>();
+        //puzzles missing the value being sorted by go last
+        fn missing_last<T: Ord>(a: Option<T>, b: Option<T>) -> Ordering {
+            match (a, b) {
+                (Some(a), Some(b)) => a.cmp(&b),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            }
+        }
+        results.sort_by(|(sa, ca, a), (sb, cb, b)| {
+            match self.sort {
+                CatalogSort::Relevance => sb.cmp(sa),
+                CatalogSort::Name => Ordering::Equal,
+                CatalogSort::Newest => {
+                    let created = |x: &PuzzleLoadingData| {
+                        Some(Reverse(x.metadata.created.clone())).filter(|x| !x.0.is_empty())
+                    };
+                    missing_last(created(a), created(b))
+                }
+                CatalogSort::Difficulty => {
+                    missing_last(a.metadata.difficulty, b.metadata.difficulty)
+                }
+                CatalogSort::Pieces => missing_last(ca.map(|x| x.pieces), cb.map(|x| x.pieces)),
+                CatalogSort::Turns => missing_last(ca.map(|x| x.turns), cb.map(|x| x.turns)),
+            }
+            .then_with(|| a.name.cmp(&b.name))
+        });
+        results.into_iter().map(|(_, _, x)| x).collect()
+    }
+    ///clear the search and filters, keeping the favorite and recent puzzles
+    pub fn clear(&mut self) {
+        *self = Self {
+            favorites: std::mem::take(&mut self.favorites),
+            recent: std::mem::take(&mut self.recent),
+            ..Self::default()
+        };
+    }
+    ///remember that a puzzle was played, moving it to the front of the recent puzzles
+    pub fn played(&mut self, key: &str) {
+        self.recent.retain(|x| x != key);
+        self.recent.insert(0, key.to_string());
+        self.recent.truncate(RECENT_COUNT);
+    }
+    ///star a puzzle, or unstar it if it already is
+    pub fn toggle_favorite(&mut self, key: &str) {
+        if self.favorites.iter().any(|x| x == key) {
+            self.favorites.retain(|x| x != key);
+        } else {
+            self.favorites.push(key.to_string());
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug)]
+///counts puzzles on a background thread, with its own runtime, so that building them doesn't freeze the app
+pub struct CountWorker {
+    receiver: std::sync::mpsc::Receiver<(String, Option<PuzzleCounts>)>,
+    pending: Vec<String>, //the keys of the puzzles it hasn't sent the counts of yet
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl CountWorker {
+    ///start counting the puzzles with these keys, loading the definitions from def_path again on the new thread
+    pub fn new(def_path: &str, exp: bool, keys: Vec<String>) -> Self {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let def_path = def_path.to_string();
+        let pending = keys.clone();
+        std::thread::spawn(move || {
+            let Ok(mut ds) = DataStorer::new(exp) else {
+                return;
+            };
+            let _ = ds.load_puzzles(&def_path);
+            let defs = ds.puzzles.lock().unwrap().defs();
+            for def in defs.iter().filter(|x| keys.contains(&x.key())) {
+                let counts = ds.count(def);
+                //stop once the app doesn't want the counts anymore
+                if sender.send((def.key(), counts)).is_err() {
+                    return;
+                }
+            }
+        });
+        Self { receiver, pending }
+    }
+    ///move the counts found so far into a map. returns whether the thread is done, in which case the puzzles it didn't
+    ///count (because they were no longer defined, or building them panicked) are marked as failed
+    fn poll(&mut self, counts: &mut HashMap<String, Option<PuzzleCounts>>) -> bool {
+        loop {
+            match self.receiver.try_recv() {
+                Ok((key, count)) => {
+                    self.pending.retain(|x| *x != key);
+                    counts.insert(key, count);
+                }
+                Err(std::sync::mpsc::TryRecvError::Empty) => return false,
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    for key in self.pending.drain(..) {
+                        counts.insert(key, None);
+                    }
+                    return true;
+                }
+            }
+        }
+    }
+}
+
+impl DataStorer {
+    ///build a puzzle to count its turns and pieces, remembering the counts (or that it failed to build)
+    pub fn count(&mut self, data: &PuzzleLoadingData) -> Option<PuzzleCounts> {
+        let key = data.key();
+        if let Some(counts) = self.counts.get(&key) {
+            return *counts;
+        }
+        let counts = data.load(&mut self.rt, HashMap::new()).ok().map(|x| {
+            let mut circles: Vec<Circle> = Vec::new();
+            for turn in x.turns.values() {
+                if !circles
+                    .iter()
+                    .any(|c| c.approx_eq(&turn.turn.circle, PRECISION))
+                {
+                    circles.push(turn.turn.circle);
+                }
+            }
+            PuzzleCounts {
+                turns: x.turns.len(),
+                circles: circles.len(),
+                pieces: x.pieces.len(),
+            }
+        });
+        self.counts.insert(key, counts);
+        counts
+    }
+    ///the puzzles that haven't been counted yet
+    fn uncounted(&self) -> Vec<PuzzleLoadingData> {
+        self.puzzles
+            .lock()
+            .unwrap()
+            .defs()
+            .into_iter()
+            .filter(|x| !self.counts.contains_key(&x.key()))
+            .collect()
+    }
+    ///collect the counts found by the background thread, and start a new one counting the rest of the puzzles once it
+    ///is done, loading the definitions from def_path. returns whether any puzzles are left to count
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn count_in_background(&mut self, def_path: &str) -> bool {
+        if let Some(counter) = &mut self.counter
+            && counter.poll(&mut self.counts)
+        {
+            self.counter = None;
+        }
+        let uncounted = self.uncounted();
+        if uncounted.is_empty() {
+            return false;
+        }
+        if self.counter.is_none() {
+            self.counter = Some(CountWorker::new(
+                def_path,
+                self.experimental,
+                uncounted.iter().map(PuzzleLoadingData::key).collect(),
+            ));
+        }
+        true
+    }
+    ///there are no threads on web, so the puzzles are never counted there. returns false
+    #[cfg(target_arch = "wasm32")]
+    pub fn count_in_background(&mut self, _def_path: &str) -> bool {
+        false
+    }
+}
@@ -3,10 +3,14 @@ use crate::{
         builtins::{circleguy_builtins, circleguy_hps_builtins, loading_builtins},
         custom_values::hpspuzzle::HPSPuzzle,
         data_storer::{
-            def_entry::DefEntry, io::*, keybind_data::KeybindData, puzzle_io::PuzzleIOData,
+            catalog::PuzzleCounts,
+            def_entry::DefEntry,
+            io::*,
+            keybind_data::{KeybindData, KeybindDiagnostic},
+            puzzle_io::PuzzleIOData,
         },
     },
-    puzzle::puzzle::{Puzzle, PuzzleData},
+    puzzle::puzzle::{Puzzle, PuzzleData, PuzzleMetadata},
 };
 use hyperpuzzlescript::{
     BUILTIN_SPAN, CustomValue, EvalCtx, FnValue, FullDiagnostic, List, Map, Runtime, Scope, Spanned,
@@ -19,12 +23,31 @@ use std::{
 };
 pub type PuzzlesMap = Arc<Mutex<DefEntry>>;
 
+///the folder the thumbnails of the puzzles are stored in
+pub const THUMBNAIL_PATH: &str = "Puzzles/Thumbnails";
+
+#[derive(Default)]
+///the thumbnails shown in the puzzle list, by PuzzleLoadingData::key. None if the puzzle has no thumbnail
+pub struct Thumbnails(pub HashMap<String, Option<egui::TextureHandle>>);
+
+impl std::fmt::Debug for Thumbnails {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.0.keys()).finish()
+    }
+}
+
 #[derive(Debug)]
 ///stores the data for loading puzzles (definitions and basic info for preview)
 pub struct DataStorer {
     pub puzzles: PuzzlesMap,
     pub rt: Runtime,
     pub keybinds: KeybindData,
+    pub keybind_diagnostics: Vec<KeybindDiagnostic>, //problems found the last time the keybinds were loaded
+    pub thumbnails: Thumbnails,                      //the thumbnails loaded so far
+    pub counts: HashMap<String, Option<PuzzleCounts>>, //the counted puzzles by PuzzleLoadingData::key, None if they failed to build
+    pub experimental: bool,                            //whether the experimental puzzles are loaded
+    #[cfg(not(target_arch = "wasm32"))]
+    pub counter: Option<crate::hps::data_storer::catalog::CountWorker>, //the thread counting the puzzles that haven't been counted yet, if any
 }
 
 #[derive(Debug, Clone)]
@@ -32,11 +55,23 @@ pub struct PuzzleLoadingData {
     pub name: String,
     pub path: PathBuf,
     pub authors: Vec<String>,
+    pub metadata: PuzzleMetadata,
     pub scramble: usize,
     pub constructor: Spanned<Arc<FnValue>>,
 }
 
 impl PuzzleLoadingData {
+    ///the path the puzzle is stored under in the definition tree (its folder and name), the same as PuzzleData::key
+    pub fn key(&self) -> String {
+        self.path
+            .with_file_name(&self.name)
+            .to_string_lossy()
+            .replace('\\', "/")
+    }
+    ///where the thumbnail of the puzzle is stored
+    pub fn thumbnail_path(&self) -> String {
+        format!("{THUMBNAIL_PATH}/{}.png", self.key())
+    }
     pub fn load(
         &self,
         rt: &mut Runtime,
@@ -70,6 +105,7 @@ impl PuzzleLoadingData {
         puz.depth = self.scramble;
         puz.keybinds = keybinds;
         puz.path = self.path.clone();
+        puz.metadata = self.metadata.clone();
         Ok(puz)
     }
 }
@@ -85,6 +121,12 @@ impl DataStorer {
             puzzles: puzzles_arc.clone(),
             rt,
             keybinds: KeybindData::new(),
+            keybind_diagnostics: Vec::new(),
+            thumbnails: Thumbnails::default(),
+            counts: HashMap::new(),
+            experimental: exp,
+            #[cfg(not(target_arch = "wasm32"))]
+            counter: None,
         };
         loading_builtins(&mut ds.rt, puzzles_arc.clone(), exp).unwrap();
         Ok(ds)
@@ -93,6 +135,22 @@ impl DataStorer {
         *self = Self::new(exp)?;
         Ok(())
     }
+    ///parse the keybind config, keeping every valid bind and storing the diagnostics for the rest.
+    ///returns Err if the config could not be parsed at all
+    fn set_keybinds(&mut self, data: String) -> Result<(), ()> {
+        match KeybindData::load_from_string(data) {
+            Ok((keybinds, diagnostics)) => {
+                self.keybinds = keybinds;
+                self.keybind_diagnostics = diagnostics;
+                Ok(())
+            }
+            Err(diagnostics) => {
+                self.keybinds = KeybindData::new();
+                self.keybind_diagnostics = diagnostics;
+                Err(())
+            }
+        }
+    }
     #[cfg(not(target_arch = "wasm32"))]
     pub fn load_puzzles(&mut self, def_path: &str) -> Result<(), ()> {
         self.rt.modules.add_from_directory(Path::new(def_path));
@@ -102,8 +160,52 @@ impl DataStorer {
     #[cfg(not(target_arch = "wasm32"))]
     pub fn load_keybinds(&mut self, kb_path: &str) -> Result<(), ()> {
         let data = read_file_to_string(kb_path).ok().ok_or(())?;
-        self.keybinds = KeybindData::load_from_string(data).ok_or(())?;
-        Ok(())
+        self.set_keybinds(data)
+    }
+    ///re-execute the definition files that changed and forget the puzzles of the deleted ones (and their counts and
+    ///thumbnails), and reload the keybinds if they changed. the runtime only executes the files added since it last
+    ///ran, so files that import a changed file are not rebuilt. returns the keys of the puzzles that were added, changed or removed
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn reload_changed(
+        &mut self,
+        def_path: &str,
+        kb_path: &str,
+        changes: &crate::hps::data_storer::watcher::FileChanges,
+    ) -> Vec<String> {
+        let mut keys = Vec::new();
+        for file in changes.changed.iter().chain(&changes.removed) {
+            let removed = self.puzzles.lock().unwrap().remove_file(file);
+            keys.extend(removed.iter().map(PuzzleLoadingData::key));
+        }
+        for file in &changes.changed {
+            match read_file_to_string(&Path::new(def_path).join(file).to_string_lossy()) {
+                Ok(contents) => {
+                    self.rt.modules.add_file(file.as_path(), contents.as_str());
+                }
+                Err(x) => log::warn!("Could not read {}: {x}", file.to_string_lossy()),
+            }
+        }
+        self.rt.exec_all_files();
+        //the puzzles of the changed files may have new names
+        for data in self.puzzles.lock().unwrap().defs() {
+            if changes
+                .changed
+                .iter()
+                .any(|x| x.with_extension("") == data.path.with_extension(""))
+            {
+                keys.push(data.key());
+            }
+        }
+        keys.sort();
+        keys.dedup();
+        self.counts.retain(|key, _| !keys.contains(key));
+        //read the thumbnails again, in case they were redrawn for the new definitions
+        self.thumbnails.0.retain(|key, _| !keys.contains(key));
+        self.counter = None; //its definitions are out of date
+        if changes.keybinds {
+            let _ = self.load_keybinds(kb_path);
+        }
+        keys
     }
     #[cfg(not(target_arch = "wasm32"))]
     pub fn load_save(&mut self, path: &str) -> Option<Puzzle> {
@@ -154,7 +256,6 @@ impl DataStorer {
             .ok_or(())?
             .contents_utf8()
             .ok_or(())?;
-        self.keybinds = KeybindData::load_from_string(data.to_string()).ok_or(())?;
-        Ok(())
+        self.set_keybinds(data.to_string())
     }
 }
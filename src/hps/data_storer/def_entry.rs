@@ -60,4 +60,31 @@ impl DefEntry {
             None
         }
     }
+    ///remove the definitions made by a file (given relative to the definition folder), and any folders left empty.
+    ///returns the definitions that were removed
+    pub fn remove_file(&mut self, file: &Path) -> Vec<PuzzleLoadingData> {
+        let Self::Folder((_, entries)) = self else {
+            return Vec::new();
+        };
+        let mut removed = Vec::new();
+        entries.retain(|_, entry| match entry {
+            Self::Def(data) if data.path.with_extension("") == file.with_extension("") => {
+                removed.push(data.clone());
+                false
+            }
+            Self::Def(_) => true,
+            Self::Folder(_) => {
+                removed.extend(entry.remove_file(file));
+                !matches!(entry, Self::Folder((_, x)) if x.is_empty())
+            }
+        });
+        removed
+    }
+    ///get all of the definitions in the tree, in no particular order
+    pub fn defs(&self) -> Vec<PuzzleLoadingData> {
+        match self {
+            Self::Def(data) => vec![data.clone()],
+            Self::Folder((_, entries)) => entries.values().flat_map(|x| x.defs()).collect(),
+        }
+    }
 }
@@ -5,10 +5,9 @@ use std::path::Path;
 const DEV: bool = true;
 
 #[cfg(not(target_arch = "wasm32"))]
-pub fn write_string_to_file(path: &Path, data: &str) -> Result<(), std::io::Error> {
-    use std::{fs, path::PathBuf};
-
-    let curr_path = match DEV {
+///the folder that paths are relative to
+fn base_path() -> String {
+    match DEV {
         //where the path is depends on if the program is being compiled or run in an EXE removed from the original folder. the DEV constant handles this
         false => String::from(
             std::env::current_exe()
@@ -17,44 +16,66 @@ pub fn write_string_to_file(path: &Path, data: &str) -> Result<(), std::io::Erro
                 .into_string()
                 .unwrap()
                 .split("circleguy.exe")
-                .into_iter()
                 .collect::<Vec<&str>>()[0],
         ),
         true => String::new(),
-    };
-    let real_path = PathBuf::from("./").join(&PathBuf::from(curr_path).join(path)); //add the path to the base path
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn write_string_to_file(path: &Path, data: &str) -> Result<(), std::io::Error> {
+    write_bytes_to_file(path, data.as_bytes())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+///write bytes to a file, making its folder if it doesn't exist yet
+pub fn write_bytes_to_file(path: &Path, data: &[u8]) -> Result<(), std::io::Error> {
+    use std::{fs, path::PathBuf};
+
+    let real_path = PathBuf::from("./").join(PathBuf::from(base_path()).join(path)); //add the path to the base path
     dbg!(&real_path);
-    fs::write(real_path, data.as_bytes())?;
+    //make the folder if it doesn't exist yet
+    if let Some(parent) = real_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(real_path, data)?;
     Ok(())
 }
 #[cfg(not(target_arch = "wasm32"))]
 ///read a file to a string for loading purposes
 pub fn read_file_to_string(path: &str) -> std::io::Result<String> {
-    let curr_path = match DEV {
-        //where the path is depends on if the program is being compiled or run in an EXE removed from the original folder. the DEV constant handles this
-        false => String::from(
-            std::env::current_exe()
-                .unwrap()
-                .into_os_string()
-                .into_string()
-                .unwrap()
-                .split("circleguy.exe")
-                .collect::<Vec<&str>>()[0],
-        ),
-        true => String::new(),
-    };
-    std::fs::read_to_string(curr_path + path) //read to a string
+    std::fs::read_to_string(base_path() + path) //read to a string
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+///read a file to bytes, for files that aren't text (like images)
+pub fn read_file_to_bytes(path: &str) -> std::io::Result<Vec<u8>> {
+    std::fs::read(base_path() + path)
 }
 
+#[cfg(target_arch = "wasm32")]
+///the files in Puzzles, included in the wasm
+static PUZZLE_FILES: include_dir::Dir<'_> =
+    include_dir::include_dir!("$CARGO_MANIFEST_DIR/Puzzles");
+
 #[cfg(target_arch = "wasm32")]
 pub fn read_file_to_string(path: &str) -> Result<String, &'static str> {
-    static PUZZLE_DEFINITIONS: include_dir::Dir<'_> =
-        include_dir::include_dir!("$CARGO_MANIFEST_DIR/Puzzles");
     let path = path.strip_prefix("Puzzles/").unwrap_or(path);
-    Ok(PUZZLE_DEFINITIONS
+    Ok(PUZZLE_FILES
         .get_file(path)
         .ok_or("no such file")?
         .contents_utf8()
         .ok_or("invalid UTF-8")?
         .to_string())
 }
+
+#[cfg(target_arch = "wasm32")]
+///read a file to bytes, for files that aren't text (like images)
+pub fn read_file_to_bytes(path: &str) -> Result<Vec<u8>, &'static str> {
+    let path = path.strip_prefix("Puzzles/").unwrap_or(path);
+    Ok(PUZZLE_FILES
+        .get_file(path)
+        .ok_or("no such file")?
+        .contents()
+        .to_vec())
+}
@@ -1,68 +1,257 @@
-use std::{
-    collections::HashMap,
-    ffi::{OsStr, OsString},
-};
+use std::{collections::HashMap, ffi::OsString, fmt::Display, path::PathBuf};
 
 use kdl::{KdlDocument, KdlNode};
 
+use crate::hps::data_storer::data_storer::PuzzleLoadingData;
+
 #[derive(Debug)]
 
 pub struct KeybindData {
     pub defaults: HashMap<egui::Key, (String, isize)>,
-    pub overrides: HashMap<OsString, HashMap<egui::Key, (String, isize)>>,
+    pub overrides: Vec<(OverrideTarget, HashMap<egui::Key, (String, isize)>)>, //stored in the order they appear in the file
+}
+
+#[derive(Debug, Clone, PartialEq)]
+///the puzzles an override block applies to
+pub enum OverrideTarget {
+    Tag(String),     //`override tag="..."`: puzzles declaring the tag in add_puzzle
+    Folder(PathBuf), //`override folder="..."`: puzzles anywhere under the folder (relative to Puzzles/Definitions)
+    Glob(String),    //`override glob="..."`: puzzles whose definition path matches the glob
+    Name(OsString),  //`override "..."`: puzzles with this definition file name or puzzle name
+}
+
+impl OverrideTarget {
+    ///check if the override applies to a puzzle
+    pub fn matches(&self, puzzle: &PuzzleLoadingData) -> bool {
+        match self {
+            Self::Tag(tag) => puzzle.metadata.tags.contains(tag),
+            Self::Folder(folder) => puzzle.path.parent().is_some_and(|x| x.starts_with(folder)),
+            Self::Glob(glob) => glob_match(glob, &puzzle.path.to_string_lossy().replace('\\', "/")),
+            Self::Name(name) => {
+                puzzle.path.file_name() == Some(name.as_os_str()) || *name == *puzzle.name
+            }
+        }
+    }
+    ///overrides with higher precedence are applied later, so their binds win.
+    ///tags < folders (deeper folders win) < globs < names
+    pub fn precedence(&self) -> usize {
+        match self {
+            Self::Tag(_) => 0,
+            Self::Folder(folder) => 1 + folder.components().count(),
+            Self::Glob(_) => usize::MAX - 1,
+            Self::Name(_) => usize::MAX,
+        }
+    }
+}
+
+///match a path against a glob. `*` matches anything within one folder, `**` matches across folders and `?` matches one character
+pub fn glob_match(glob: &str, path: &str) -> bool {
+    fn matches(glob: &[char], path: &[char]) -> bool {
+        match glob {
+            [] => path.is_empty(),
+            ['*', '*', '/', rest @ ..] => (0..=path.len())
+                .filter(|&i| i == 0 || path[i - 1] == '/')
+                .any(|i| matches(rest, &path[i..])),
+            ['*', '*', rest @ ..] => (0..=path.len()).any(|i| matches(rest, &path[i..])),
+            ['*', rest @ ..] => (0..=path.len())
+                .take_while(|&i| i == 0 || path[i - 1] != '/')
+                .any(|i| matches(rest, &path[i..])),
+            ['?', rest @ ..] => {
+                path.first().is_some_and(|x| *x != '/') && matches(rest, &path[1..])
+            }
+            [c, rest @ ..] => path.first() == Some(c) && matches(rest, &path[1..]),
+        }
+    }
+    matches(
+        &glob.chars().collect::<Vec<char>>(),
+        &path.chars().collect::<Vec<char>>(),
+    )
+}
+
+#[derive(Debug, Clone)]
+///a problem found while loading the keybind config, located by line and column (both 1-based)
+pub struct KeybindDiagnostic {
+    pub line: usize,
+    pub col: usize,
+    pub msg: String,
+}
+
+impl Display for KeybindDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "keybinds {}:{}: {}", self.line, self.col, self.msg)
+    }
+}
+
+impl KeybindDiagnostic {
+    ///make a diagnostic at a byte offset into the source
+    fn at(src: &str, offset: usize, msg: String) -> Self {
+        let before = &src[..offset.min(src.len())];
+        let line = before.matches('\n').count() + 1;
+        let col = before.len() - before.rfind('\n').map_or(0, |x| x + 1) + 1;
+        Self { line, col, msg }
+    }
 }
 
 impl KeybindData {
     pub fn new() -> Self {
         Self {
             defaults: HashMap::new(),
-            overrides: HashMap::new(),
+            overrides: Vec::new(),
         }
     }
-    pub fn load_from_string(data: String) -> Option<Self> {
-        fn parse_turn_node(node: &KdlNode) -> Option<(egui::Key, (String, isize))> {
-            Some((
-                egui::Key::from_name(node.name().value())?,
-                (
-                    node.entries().get(0)?.value().as_string()?.to_string(),
-                    node.entries().get(1)?.value().as_integer()? as isize,
-                ),
-            ))
+    ///load keybinds from the contents of a config file.
+    ///invalid binds are skipped and reported, and all of the valid binds are kept.
+    ///returns Err only if the file is not valid kdl at all
+    pub fn load_from_string(
+        data: String,
+    ) -> Result<(Self, Vec<KeybindDiagnostic>), Vec<KeybindDiagnostic>> {
+        ///parse a single bind, like `j L 2`
+        fn parse_turn_node(
+            src: &str,
+            node: &KdlNode,
+        ) -> Result<(egui::Key, (String, isize)), KeybindDiagnostic> {
+            let name = node.name().value();
+            let err = |offset: usize, msg: String| KeybindDiagnostic::at(src, offset, msg);
+            let key = egui::Key::from_name(name).ok_or_else(|| {
+                err(
+                    node.name().span().offset(),
+                    format!("unknown key name `{name}`"),
+                )
+            })?;
+            let turn = node.entries().first().ok_or_else(|| {
+                err(
+                    node.span().offset(),
+                    format!("bind for `{name}` is missing a turn name"),
+                )
+            })?;
+            let turn_name = turn.value().as_string().ok_or_else(|| {
+                err(
+                    turn.span().offset(),
+                    format!("turn name for `{name}` should be a string"),
+                )
+            })?;
+            let mult = node.entries().get(1).ok_or_else(|| {
+                err(
+                    node.span().offset(),
+                    format!("bind for `{name}` is missing a multiplier"),
+                )
+            })?;
+            let mult_val = mult.value().as_integer().ok_or_else(|| {
+                err(
+                    mult.span().offset(),
+                    format!("multiplier for `{name}` should be an integer"),
+                )
+            })?;
+            Ok((key, (turn_name.to_string(), mult_val as isize)))
+        }
+        ///parse all the binds in the children of a block, reporting duplicates within the block and keys already
+        ///bound by earlier blocks ('seen')
+        fn parse_block(
+            src: &str,
+            node: &KdlNode,
+            seen: &HashMap<egui::Key, (String, isize)>,
+            diagnostics: &mut Vec<KeybindDiagnostic>,
+        ) -> HashMap<egui::Key, (String, isize)> {
+            let mut binds = HashMap::new();
+            let Some(children) = node.children() else {
+                diagnostics.push(KeybindDiagnostic::at(
+                    src,
+                    node.span().offset(),
+                    format!("`{}` block has no body", node.name().value()),
+                ));
+                return binds;
+            };
+            for c in children.nodes() {
+                match parse_turn_node(src, c) {
+                    Ok((k, v)) => {
+                        if binds.insert(k, v).is_some() || seen.contains_key(&k) {
+                            diagnostics.push(KeybindDiagnostic::at(
+                                src,
+                                c.span().offset(),
+                                format!("duplicate binding for `{}`", c.name().value()),
+                            ));
+                        }
+                    }
+                    Err(diag) => diagnostics.push(diag),
+                }
+            }
+            binds
+        }
+        ///parse what an override block applies to from its first entry
+        fn parse_target(node: &KdlNode) -> Option<OverrideTarget> {
+            let entry = node.entries().first()?;
+            let value = entry.value().as_string()?;
+            Some(match entry.name().map(|x| x.value()) {
+                None => OverrideTarget::Name(OsString::from(value)),
+                Some("folder") => OverrideTarget::Folder(PathBuf::from(value)),
+                Some("glob") => OverrideTarget::Glob(value.to_string()),
+                Some("tag") => OverrideTarget::Tag(value.to_string()),
+                Some(_) => return None,
+            })
         }
+        let kdl = data.parse::<KdlDocument>().map_err(|e| {
+            e.diagnostics
+                .iter()
+                .map(|d| {
+                    KeybindDiagnostic::at(
+                        &data,
+                        d.span.offset(),
+                        d.message.clone().unwrap_or(String::from("invalid kdl")),
+                    )
+                })
+                .collect::<Vec<KeybindDiagnostic>>()
+        })?;
+        let mut diagnostics = Vec::new();
         let mut binds = HashMap::new();
-        let mut overrides = HashMap::new();
-        let kdl = data.parse::<KdlDocument>().ok()?;
+        let mut overrides = Vec::new();
         for node in kdl.nodes() {
             match node.name().value() {
                 "binds" => {
-                    for c in node.children()?.nodes() {
-                        let t = parse_turn_node(c)?;
-                        binds.insert(t.0, t.1);
-                    }
+                    let block = parse_block(&data, node, &binds, &mut diagnostics);
+                    binds.extend(block);
                 }
                 "override" => {
-                    let name = OsString::from(node.entries().get(0)?.value().as_string()?);
-                    let mut over = HashMap::new();
-                    for c in node.children()?.nodes() {
-                        let t = parse_turn_node(c)?;
-                        over.insert(t.0, t.1);
-                    }
-                    overrides.insert(name, over);
+                    let Some(target) = parse_target(node) else {
+                        diagnostics.push(KeybindDiagnostic::at(
+                            &data,
+                            node.span().offset(),
+                            String::from(
+                                "`override` block needs a puzzle name, or one of `folder`, `glob` or `tag`",
+                            ),
+                        ));
+                        continue;
+                    };
+                    let over = parse_block(&data, node, &HashMap::new(), &mut diagnostics);
+                    overrides.push((target, over));
                 }
-                _ => {}
+                other => diagnostics.push(KeybindDiagnostic::at(
+                    &data,
+                    node.span().offset(),
+                    format!("unknown block `{other}`"),
+                )),
             }
         }
-        Some(Self {
-            defaults: binds,
-            overrides,
-        })
+        Ok((
+            Self {
+                defaults: binds,
+                overrides,
+            },
+            diagnostics,
+        ))
     }
-    pub fn get_keybinds_for_puzzle(&self, name: &OsStr) -> HashMap<egui::Key, (String, isize)> {
-        let mut binds = HashMap::new();
-        for (k, v) in &self.defaults {
-            binds.insert(*k, v.clone());
-        }
-        if let Some(b) = self.overrides.get(name) {
+    ///get the keybinds for a puzzle: the defaults, with every matching override applied on top in order of precedence
+    pub fn get_keybinds_for_puzzle(
+        &self,
+        puzzle: &PuzzleLoadingData,
+    ) -> HashMap<egui::Key, (String, isize)> {
+        let mut binds = self.defaults.clone();
+        let mut matching = self
+            .overrides
+            .iter()
+            .filter(|(target, _)| target.matches(puzzle))
+            .collect::<Vec<_>>();
+        matching.sort_by_key(|(target, _)| target.precedence()); //stable, so ties keep file order
+        for (_, b) in matching {
             for (k, v) in b {
                 binds.insert(*k, v.clone());
             }
@@ -1,5 +1,9 @@
+pub mod catalog;
 pub mod data_storer;
 pub mod def_entry;
 pub mod io;
 pub mod keybind_data;
 pub mod puzzle_io;
+pub mod stats;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod watcher;
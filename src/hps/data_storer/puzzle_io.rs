@@ -4,6 +4,7 @@ use kdl::KdlDocument;
 
 use crate::{hps::data_storer::data_storer::DataStorer, puzzle::puzzle::Puzzle};
 
+#[derive(Debug, Clone)]
 pub struct PuzzleIOData {
     pub name: String,
     pub path: PathBuf,
@@ -21,14 +22,17 @@ impl Puzzle {
         }
     }
     pub fn from_io_data(data: PuzzleIOData, ds: &mut DataStorer) -> Option<Puzzle> {
+        //logs store the file the puzzle is defined in, but definitions are stored under their folder and puzzle name
+        let loading_data = ds
+            .puzzles
+            .lock()
+            .unwrap()
+            .get(&data.path.with_file_name(&data.name))?;
         let mut p = Puzzle::new(
-            ds.puzzles
-                .lock()
-                .unwrap()
-                .get(&data.path)?
+            loading_data
                 .load(
                     &mut ds.rt,
-                    ds.keybinds.get_keybinds_for_puzzle(&data.path.file_name()?),
+                    ds.keybinds.get_keybinds_for_puzzle(&loading_data),
                 )
                 .ok()?,
         );
@@ -68,7 +72,10 @@ impl PuzzleIOData {
         string
     }
     pub fn from_string(string: String) -> Option<Self> {
-        let kdl = string.parse::<KdlDocument>().ok()?;
+        Self::from_kdl(&string.parse::<KdlDocument>().ok()?)
+    }
+    ///read the data from a parsed kdl document, like the contents of a log file
+    pub fn from_kdl(kdl: &KdlDocument) -> Option<Self> {
         Some(Self {
             path: PathBuf::from(
                 kdl.get("path")?
@@ -0,0 +1,140 @@
+use std::{collections::HashMap, path::Path};
+
+use crate::hps::data_storer::{catalog::PuzzleCounts, data_storer::PuzzleLoadingData};
+
+///how many authors and puzzles are listed as the top contributors and the most complex puzzles
+pub const TOP: usize = 5;
+
+#[derive(Debug, Clone, Default)]
+///statistics about the puzzles in the catalog. the pieces and circles are only known for the puzzles that were built
+pub struct CatalogStats {
+    pub puzzles: usize,                     //the number of puzzles
+    pub authors: HashMap<String, usize>,    //the number of puzzles by each author
+    pub folders: HashMap<String, usize>,    //the number of puzzles in each folder
+    pub circles: HashMap<usize, usize>,     //the number of puzzles with each number of turn circles
+    pub pieces: usize, //the total number of pieces of the puzzles that were built
+    pub built: Vec<(String, PuzzleCounts)>, //the key and counts of each puzzle that was built
+    pub failed: usize, //the number of puzzles that failed to build
+    pub uncounted: usize, //the number of puzzles that haven't been built yet
+}
+
+impl CatalogStats {
+    ///the statistics of the definitions, using the counts of the ones that were built (see DataStorer::count)
+    pub fn new(defs: &[PuzzleLoadingData], counts: &HashMap<String, Option<PuzzleCounts>>) -> Self {
+        let mut stats = Self::default();
+        for def in defs {
+            let key = def.key();
+            stats.add(&key, &def.authors, counts.get(&key).copied());
+        }
+        stats
+    }
+    ///add a puzzle, by its key, its authors and its counts. the counts are None if it hasn't been built yet, and
+    ///Some(None) if it failed to build
+    pub fn add(&mut self, key: &str, authors: &[String], counts: Option<Option<PuzzleCounts>>) {
+        self.puzzles += 1;
+        for author in authors {
+            *self.authors.entry(author.clone()).or_default() += 1;
+        }
+        let folder = Path::new(key)
+            .parent()
+            .map(|x| x.to_string_lossy().to_string())
+            .unwrap_or_default();
+        *self.folders.entry(folder).or_default() += 1;
+        match counts {
+            None => self.uncounted += 1,
+            Some(None) => self.failed += 1,
+            Some(Some(counts)) => {
+                *self.circles.entry(counts.circles).or_default() += 1;
+                self.pieces += counts.pieces;
+                self.built.push((key.to_string(), counts));
+            }
+        }
+    }
+    ///the authors with the most puzzles, most first, then by name
+    pub fn top_authors(&self, n: usize) -> Vec<(&str, usize)> {
+        let mut authors = self
+            .authors
+            .iter()
+            .map(|(name, count)| (name.as_str(), *count))
+            .collect::<Vec<_>>();
+        authors.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        authors.truncate(n);
+        authors
+    }
+    ///the number of puzzles in each folder, by folder
+    pub fn folders(&self) -> Vec<(&str, usize)> {
+        let mut folders = self
+            .folders
+            .iter()
+            .map(|(name, count)| (name.as_str(), *count))
+            .collect::<Vec<_>>();
+        folders.sort();
+        folders
+    }
+    ///the number of puzzles with each number of turn circles, by number of circles
+    pub fn circles(&self) -> Vec<(usize, usize)> {
+        let mut circles = self.circles.clone().into_iter().collect::<Vec<_>>();
+        circles.sort();
+        circles
+    }
+    ///the puzzles with the most pieces (then the most turns), most first
+    pub fn most_complex(&self, n: usize) -> Vec<(&str, PuzzleCounts)> {
+        let mut built = self
+            .built
+            .iter()
+            .map(|(key, counts)| (key.as_str(), *counts))
+            .collect::<Vec<_>>();
+        built.sort_by(|(ka, a), (kb, b)| {
+            (b.pieces, b.turns)
+                .cmp(&(a.pieces, a.turns))
+                .then(ka.cmp(kb))
+        });
+        built.truncate(n);
+        built
+    }
+}
+
+impl std::fmt::Display for CatalogStats {
+    ///write the statistics as a plain text report
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{} puzzles by {} authors",
+            self.puzzles,
+            self.authors.len()
+        )?;
+        write!(
+            f,
+            "{} pieces in the {} puzzles built",
+            self.pieces,
+            self.built.len()
+        )?;
+        if self.failed > 0 {
+            write!(f, " ({} failed to build)", self.failed)?;
+        }
+        if self.uncounted > 0 {
+            write!(f, " ({} not built yet)", self.uncounted)?;
+        }
+        write!(f, "\n\ntop puzzle contributors:\n")?;
+        for (author, count) in self.top_authors(TOP) {
+            writeln!(f, "\t{author}: {count}")?;
+        }
+        write!(f, "\npuzzles per folder:\n")?;
+        for (folder, count) in self.folders() {
+            writeln!(f, "\t{folder}: {count}")?;
+        }
+        write!(f, "\npuzzles per number of circles:\n")?;
+        for (circles, count) in self.circles() {
+            writeln!(f, "\t{circles}: {count}")?;
+        }
+        write!(f, "\nmost complex puzzles:\n")?;
+        for (key, counts) in self.most_complex(TOP) {
+            writeln!(
+                f,
+                "\t{key}: {} pieces, {} turns",
+                counts.pieces, counts.turns
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+///how often the definitions and the keybind config are checked for changes
+pub const WATCH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+#[derive(Debug, Default, PartialEq)]
+///the files a FileWatcher found changed since it last looked
+pub struct FileChanges {
+    pub changed: Vec<PathBuf>, //the definition files that were added or modified, relative to the definition folder
+    pub removed: Vec<PathBuf>, //the definition files that were deleted, relative to the definition folder
+    pub keybinds: bool,        //whether the keybind config was modified
+}
+
+impl FileChanges {
+    ///whether nothing changed
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty() && self.removed.is_empty() && !self.keybinds
+    }
+}
+
+#[derive(Debug)]
+///watches the puzzle definitions and the keybind config for changes, by comparing when they were last modified
+pub struct FileWatcher {
+    def_path: PathBuf,
+    kb_path: PathBuf,
+    defs: HashMap<PathBuf, SystemTime>, //when each definition file was last modified, relative to def_path
+    keybinds: Option<SystemTime>, //when the keybind config was last modified, None if it couldn't be read
+    last_check: web_time::Instant, //when the files were last checked
+}
+
+///when a file was last modified, None if it can't be read
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|x| x.modified()).ok()
+}
+
+///add every definition file in a folder and its subfolders to a map, with when they were last modified.
+///'root' is the folder the paths are made relative to
+fn scan(root: &Path, dir: &Path, files: &mut HashMap<PathBuf, SystemTime>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            scan(root, &path, files);
+        } else if path.extension().is_some_and(|x| x == "hps")
+            && let Some(time) = modified(&path)
+            && let Ok(relative) = path.strip_prefix(root)
+        {
+            files.insert(relative.to_path_buf(), time);
+        }
+    }
+}
+
+impl FileWatcher {
+    ///start watching a definition folder and a keybind config, taking the files as they are now as unchanged
+    pub fn new(def_path: &str, kb_path: &str) -> Self {
+        let mut watcher = Self {
+            def_path: PathBuf::from(def_path),
+            kb_path: PathBuf::from(kb_path),
+            defs: HashMap::new(),
+            keybinds: None,
+            last_check: web_time::Instant::now(),
+        };
+        watcher.changes();
+        watcher
+    }
+    ///the files that changed since the last time they were checked
+    pub fn changes(&mut self) -> FileChanges {
+        let mut defs = HashMap::new();
+        scan(&self.def_path, &self.def_path, &mut defs);
+        let mut changes = FileChanges {
+            changed: defs
+                .iter()
+                .filter(|(path, time)| self.defs.get(*path) != Some(*time))
+                .map(|(path, _)| path.clone())
+                .collect(),
+            removed: self
+                .defs
+                .keys()
+                .filter(|x| !defs.contains_key(*x))
+                .cloned()
+                .collect(),
+            keybinds: false,
+        };
+        changes.changed.sort();
+        changes.removed.sort();
+        let keybinds = modified(&self.kb_path);
+        changes.keybinds = keybinds != self.keybinds;
+        self.defs = defs;
+        self.keybinds = keybinds;
+        self.last_check = web_time::Instant::now();
+        changes
+    }
+    ///the files that changed, if it is time to check again and any did
+    pub fn poll(&mut self) -> Option<FileChanges> {
+        if self.last_check.elapsed() < WATCH_INTERVAL {
+            return None;
+        }
+        Some(self.changes()).filter(|x| !x.is_empty())
+    }
+}
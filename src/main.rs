@@ -1,5 +1,7 @@
 //#![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 pub mod complex;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod headless;
 pub mod hps;
 pub mod puzzle;
 #[cfg(test)]
@@ -31,6 +33,11 @@ pub const DETAIL: f64 = 25.0;
 // When compiling natively:
 #[cfg(not(target_arch = "wasm32"))]
 fn main() -> eframe::Result {
+    //if any arguments are passed, run the corresponding command without opening a window
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+    if !args.is_empty() {
+        std::process::exit(headless::run(&args));
+    }
     // set the icon
     let icon_data =
         eframe::icon_data::from_png_bytes(ICON_PNG_DATA).expect("error loading application icon");
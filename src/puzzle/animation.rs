@@ -0,0 +1,148 @@
+use crate::puzzle::{
+    color::ColorScheme,
+    puzzle::Puzzle,
+    raster::{Image, RasterOptions, rasterize_pieces},
+    render_piece::bounds,
+};
+
+#[derive(Debug, Clone, Copy)]
+///options for animating a sequence of turns
+pub struct AnimationOptions {
+    pub raster: RasterOptions, //how each frame is drawn
+    pub frames_per_turn: u32,  //how many frames each turn is animated over
+    pub fps: u16,              //how many frames are shown per second
+    pub hold: u16,             //how long the first and last frames are shown, in milliseconds
+    pub cut: bool,             //whether the turns cut the puzzle
+}
+
+impl Default for AnimationOptions {
+    fn default() -> Self {
+        Self {
+            raster: RasterOptions::default(),
+            frames_per_turn: 10,
+            fps: 30,
+            hold: 1000,
+            cut: false,
+        }
+    }
+}
+
+impl Puzzle {
+    ///read a sequence of turns separated by spaces. each turn is a turn id, optionally followed by how many times to do
+    ///it and a `'` to do it the other way, like `R`, `UL2` or `D'`
+    pub fn parse_moves(&self, moves: &str) -> Result<Vec<(String, isize)>, String> {
+        moves
+            .split_whitespace()
+            .map(|x| self.parse_move(x))
+            .collect()
+    }
+    ///read a single turn of a sequence. ids that end in numbers or `'` are still read as a whole
+    fn parse_move(&self, turn: &str) -> Result<(String, isize), String> {
+        if self.turns.contains_key(turn) {
+            return Ok((turn.to_string(), 1));
+        }
+        let (rest, sign) = match turn.strip_suffix('\'') {
+            Some(rest) => (rest, -1),
+            None => (turn, 1),
+        };
+        let id = rest.trim_end_matches(|x: char| x.is_ascii_digit());
+        let mult = if id.len() == rest.len() {
+            1
+        } else {
+            rest[id.len()..]
+                .parse::<isize>()
+                .map_err(|_| format!("{turn} is not a valid turn"))?
+        };
+        if !self.turns.contains_key(id) {
+            return Err(format!("no turn named {id}"));
+        }
+        Ok((id.to_string(), sign * mult))
+    }
+    ///draw every frame of doing a sequence of turns from the current state, animated like in the app. the first frame
+    ///is the current state, and each turn adds frames_per_turn frames. every frame has the same size and bounds, so the
+    ///puzzle doesn't move between frames. the puzzle itself is not changed
+    pub fn animate(
+        &self,
+        moves: &[(String, isize)],
+        options: &AnimationOptions,
+        scheme: &ColorScheme,
+    ) -> Result<Vec<Image>, String> {
+        let mut puzzle = self.clone();
+        puzzle.animation_offset = None;
+        puzzle.anim_left = 0.0;
+        //do the turns first, to find the bounds of every state
+        let mut states = vec![puzzle.clone()];
+        for (id, mult) in moves {
+            if !puzzle.turn_id(id, options.cut, *mult)? {
+                return Err(format!("turn {id} is bandaged"));
+            }
+            states.push(puzzle.clone());
+        }
+        let total_bounds = states
+            .iter()
+            .map(|x| bounds(&x.pieces))
+            .reduce(|a, b| {
+                [
+                    a[0].min(b[0]),
+                    a[1].min(b[1]),
+                    a[2].max(b[2]),
+                    a[3].max(b[3]),
+                ]
+            })
+            .unwrap_or(bounds(&[]));
+        let frames_per_turn = options.frames_per_turn.max(1);
+        let mut frames = vec![rasterize_pieces(
+            &states[0].pieces,
+            total_bounds,
+            &options.raster,
+            scheme,
+        )];
+        for state in &mut states[1..] {
+            for i in 1..=frames_per_turn {
+                state.anim_left = 1.0 - i as f32 / frames_per_turn as f32;
+                frames.push(rasterize_pieces(
+                    &state.animated_pieces(),
+                    total_bounds,
+                    &options.raster,
+                    scheme,
+                ));
+            }
+        }
+        Ok(frames)
+    }
+}
+
+///encode frames (which all have to be the same size) as an animated png that loops forever. the first and last frames
+///are shown for the hold time, and the rest at the frame rate
+pub fn encode_apng(frames: &[Image], options: &AnimationOptions) -> Result<Vec<u8>, String> {
+    let first = frames.first().ok_or("there are no frames to encode")?;
+    if frames
+        .iter()
+        .any(|x| (x.width, x.height) != (first.width, first.height))
+    {
+        return Err(String::from("all of the frames must be the same size"));
+    }
+    let mut bytes = Vec::new();
+    let mut encoder = png::Encoder::new(&mut bytes, first.width, first.height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .set_animated(frames.len() as u32, 0)
+        .map_err(|err| err.to_string())?;
+    let mut writer = encoder.write_header().map_err(|err| err.to_string())?;
+    for (i, frame) in frames.iter().enumerate() {
+        let (numerator, denominator) = if i == 0 || i == frames.len() - 1 {
+            (options.hold, 1000)
+        } else {
+            (1, options.fps.max(1))
+        };
+        writer
+            .set_frame_delay(numerator, denominator)
+            .map_err(|err| err.to_string())?;
+        writer
+            .write_image_data(frame.as_bytes())
+            .map_err(|err| err.to_string())?;
+    }
+    writer.finish().map_err(|err| err.to_string())?;
+    Ok(bytes)
+}
@@ -1,27 +1,123 @@
-#[derive(Clone, Debug, Copy, PartialEq)]
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Hash)]
+///the color of a piece
 pub enum Color {
-    Red,
-    Green,
-    Blue,
-    Yellow,
-    Purple,
-    Gray,
-    Black,
-    Brown,
-    Cyan,
-    White,
-    DarkBlue,
-    DarkGreen,
-    DarkGray,
-    DarkRed,
-    LightBlue,
-    LightGray,
-    LightGreen,
-    LightYellow,
-    LightRed,
-    Khaki,
-    Gold,
-    Magenta,
-    Orange,
-    None,
+    Rgba([u8; 4]), //a color given by its red, green, blue and alpha values
+    None,          //the color of pieces that were never colored
+}
+
+///the builtin colors, by the name they have in definitions. the values are the same as the constants in egui::Color32
+pub const NAMED_COLORS: [(&str, Color); 23] = [
+    ("red", Color::rgb(255, 0, 0)),
+    ("green", Color::rgb(0, 255, 0)),
+    ("blue", Color::rgb(0, 0, 255)),
+    ("yellow", Color::rgb(255, 255, 0)),
+    ("purple", Color::rgb(0x80, 0, 0x80)),
+    ("gray", Color::rgb(160, 160, 160)),
+    ("black", Color::rgb(0, 0, 0)),
+    ("brown", Color::rgb(165, 42, 42)),
+    ("cyan", Color::rgb(0, 255, 255)),
+    ("white", Color::rgb(255, 255, 255)),
+    ("dark_blue", Color::rgb(0, 0, 0x8B)),
+    ("dark_green", Color::rgb(0, 0x64, 0)),
+    ("dark_gray", Color::rgb(96, 96, 96)),
+    ("dark_red", Color::rgb(0x8B, 0, 0)),
+    ("light_blue", Color::rgb(0xAD, 0xD8, 0xE6)),
+    ("light_gray", Color::rgb(220, 220, 220)),
+    ("light_green", Color::rgb(0x90, 0xEE, 0x90)),
+    ("light_red", Color::rgb(255, 128, 128)),
+    ("light_yellow", Color::rgb(255, 255, 0xE0)),
+    ("khaki", Color::rgb(240, 230, 140)),
+    ("gold", Color::rgb(255, 215, 0)),
+    ("magenta", Color::rgb(255, 0, 255)),
+    ("orange", Color::rgb(255, 165, 0)),
+];
+
+impl Color {
+    ///make an opaque color from its red, green and blue values
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self::Rgba([r, g, b, 255])
+    }
+    ///parse a color written like `#rrggbb`, `#rrggbbaa` or `#rgb`. the `#` is optional
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let hex = hex.strip_prefix('#').unwrap_or(hex);
+        if !hex.chars().all(|x| x.is_ascii_hexdigit()) {
+            return None;
+        }
+        let channel = |i: usize, len: usize| {
+            u8::from_str_radix(&hex[i * len..(i + 1) * len], 16)
+                .ok()
+                .map(|x| if len == 1 { x * 17 } else { x })
+        };
+        Some(match hex.len() {
+            3 => Self::rgb(channel(0, 1)?, channel(1, 1)?, channel(2, 1)?),
+            6 => Self::rgb(channel(0, 2)?, channel(1, 2)?, channel(2, 2)?),
+            8 => Self::Rgba([
+                channel(0, 2)?,
+                channel(1, 2)?,
+                channel(2, 2)?,
+                channel(3, 2)?,
+            ]),
+            _ => return None,
+        })
+    }
+    ///write the color like `#rrggbb`, or `#rrggbbaa` if it is transparent
+    pub fn to_hex(&self) -> Option<String> {
+        match self {
+            Self::Rgba([r, g, b, 255]) => Some(format!("#{r:02x}{g:02x}{b:02x}")),
+            Self::Rgba([r, g, b, a]) => Some(format!("#{r:02x}{g:02x}{b:02x}{a:02x}")),
+            Self::None => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+///a pattern or glyph drawn over the pieces of a color, so colors can be told apart without seeing them
+pub enum Marking {
+    Plain,         //nothing is drawn
+    Stripes,       //parallel diagonal lines
+    Dots,          //a grid of dots
+    Hatching,      //diagonal lines crossing each other
+    Glyph(String), //text drawn in the middle of each part of the piece
+}
+
+impl Marking {
+    ///the marking given to the nth color of a puzzle when the user hasn't chosen one
+    pub fn nth_default(n: usize) -> Self {
+        Self::Glyph((n + 1).to_string())
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+///a user's replacements for the colors of a puzzle. only changes how pieces are drawn, not the pieces themselves
+pub struct ColorScheme {
+    pub colors: HashMap<Color, Color>, //the color to draw pieces of each color with
+    pub markings: HashMap<Color, Marking>, //the marking drawn over pieces of each color
+}
+
+impl ColorScheme {
+    ///the color a piece of this color should be drawn with
+    pub fn apply(&self, color: Color) -> Color {
+        self.colors.get(&color).copied().unwrap_or(color)
+    }
+    ///the marking drawn over a piece of this color, if any
+    pub fn marking(&self, color: Color) -> Option<&Marking> {
+        self.markings.get(&color).filter(|x| **x != Marking::Plain)
+    }
+    ///combine two schemes, with the replacements in `over` taking priority
+    pub fn layered(&self, over: &ColorScheme) -> ColorScheme {
+        let mut scheme = self.clone();
+        scheme
+            .colors
+            .extend(over.colors.iter().map(|(k, v)| (*k, *v)));
+        scheme
+            .markings
+            .extend(over.markings.iter().map(|(k, v)| (*k, v.clone())));
+        scheme
+    }
+    ///whether the scheme changes nothing
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty() && self.markings.is_empty()
+    }
 }
@@ -0,0 +1,207 @@
+use approx_collections::ApproxEq;
+
+use crate::{
+    PRECISION,
+    complex::complex_circle::Contains,
+    puzzle::{color::Color, piece::Piece, puzzle::Puzzle, region::Region},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///how the pieces a filter doesn't select are drawn
+pub enum FilterStyle {
+    Hidden,      //not drawn at all
+    Translucent, //drawn faintly, without outlines
+    Gray,        //drawn in gray, without markings
+}
+
+#[derive(Debug, Clone)]
+///a preset choosing which pieces of a puzzle are drawn normally. a piece is selected if it matches every criterion
+pub struct PieceFilter {
+    pub name: String,           //the name shown in the filters menu
+    pub colors: Vec<Color>, //the colors of the selected pieces. if empty, every color is selected
+    pub region: Region,     //the region the selected pieces belong in, in the solved state
+    pub orbits: Vec<usize>, //the orbits of the selected pieces, numbered by orbits(). if empty, every orbit is
+    pub style: FilterStyle, //how the rest of the pieces are drawn
+    pub key: Option<egui::Key>, //the key that turns the filter on and off
+}
+
+impl PieceFilter {
+    ///make a filter that selects every piece
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            colors: Vec::new(),
+            region: Region::everywhere(),
+            orbits: Vec::new(),
+            style: FilterStyle::Translucent,
+            key: None,
+        }
+    }
+    ///whether the filter needs to know where pieces belong (which is slow to find)
+    fn needs_homes(&self) -> bool {
+        !self.region.is_everywhere() || !self.orbits.is_empty()
+    }
+}
+
+impl Puzzle {
+    ///each piece moved back to where it was in the solved state, by undoing the solve and the scramble on it alone
+    pub fn home_pieces(&self) -> Vec<Piece> {
+        let undo = self
+            .stack
+            .iter()
+            .rev()
+            .map(|(id, mult)| (id, *mult))
+            .chain(
+                self.scramble
+                    .iter()
+                    .flat_map(|x| x.iter().rev().map(|id| (id, 1))),
+            )
+            .filter_map(|(id, mult)| Some(self.turns.get(id)?.inverse().mult(mult)))
+            .collect::<Vec<_>>();
+        self.pieces
+            .iter()
+            .map(|piece| {
+                undo.iter().fold(piece.piece.clone(), |piece, turn| {
+                    turn.turn_piece(&piece).unwrap_or(piece)
+                })
+            })
+            .collect()
+    }
+    ///the index of the piece of the solved state a piece (moved back to the solved state) is part of, if any
+    pub fn solved_index(&self, home: &Piece) -> Option<usize> {
+        let pieces = &self.data.pieces;
+        pieces
+            .iter()
+            .position(|x| x.shape.approx_eq(&home.shape, PRECISION))
+            .or_else(|| {
+                //the piece was cut off of a solved piece, so find the one containing it
+                let center = home.shape.calculate_components().first()?.barycenter();
+                pieces
+                    .iter()
+                    .position(|x| x.shape.contains(center) == Contains::Inside)
+            })
+    }
+    ///the orbit of each piece of the solved state, numbered from 0 in the order they first appear. two pieces are in the
+    ///same orbit if the turns can move one to where the other is. pieces that turns jumble are only in their own orbit
+    pub fn orbits(&self) -> Vec<usize> {
+        let pieces = &self.data.pieces;
+        //union find, where each piece points towards the first piece of its orbit
+        let mut parent = (0..pieces.len()).collect::<Vec<usize>>();
+        fn root(parent: &[usize], mut i: usize) -> usize {
+            while parent[i] != i {
+                i = parent[i];
+            }
+            i
+        }
+        for (i, piece) in pieces.iter().enumerate() {
+            for turn in self.turns.values() {
+                let Some(turned) = turn.turn_piece(piece) else {
+                    continue;
+                };
+                if let Some(j) = pieces.iter().position(|x| {
+                    x.shape.border.len() == turned.shape.border.len()
+                        && x.shape.approx_eq(&turned.shape, PRECISION)
+                }) {
+                    let (a, b) = (root(&parent, i), root(&parent, j));
+                    parent[a.max(b)] = a.min(b);
+                }
+            }
+        }
+        //number the orbits in order
+        let mut numbers = Vec::new();
+        (0..pieces.len())
+            .map(|i| {
+                let r = root(&parent, i);
+                match numbers.iter().position(|x| *x == r) {
+                    Some(n) => n,
+                    None => {
+                        numbers.push(r);
+                        numbers.len() - 1
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+///the key, stack, scramble and number of pieces of a puzzle, which tell whether it has changed
+type PuzzleState = (String, Vec<(String, isize)>, Option<Vec<String>>, usize);
+
+#[derive(Debug, Clone, Default)]
+///the slow parts of filtering and inspecting pieces, kept until the puzzle changes
+pub struct FilterCache {
+    puzzle: Option<(String, usize)>, //the key and number of solved pieces of the puzzle the orbits were found for
+    orbits: Vec<usize>,              //the orbit of each piece of the solved state
+    state: Option<PuzzleState>,      //the state of the puzzle the homes were found for
+    homes: Vec<(Piece, Option<usize>)>, //each piece moved back to the solved state, and the solved piece it is part of
+}
+
+impl FilterCache {
+    ///the orbit of each piece of the solved state of the puzzle
+    pub fn orbits(&mut self, puzzle: &Puzzle) -> &[usize] {
+        let id = (puzzle.data.key(), puzzle.data.pieces.len());
+        if self.puzzle.as_ref() != Some(&id) {
+            self.orbits = puzzle.orbits();
+            self.puzzle = Some(id);
+        }
+        &self.orbits
+    }
+    ///find where each piece of the puzzle belongs, if the puzzle has changed since the last time
+    fn update_homes(&mut self, puzzle: &Puzzle) {
+        if let Some((key, stack, scramble, count)) = &self.state
+            && *key == puzzle.data.key()
+            && *stack == puzzle.stack
+            && *scramble == puzzle.scramble
+            && *count == puzzle.pieces.len()
+        {
+            return;
+        }
+        self.homes = puzzle
+            .home_pieces()
+            .into_iter()
+            .map(|x| {
+                let index = puzzle.solved_index(&x);
+                (x, index)
+            })
+            .collect();
+        self.state = Some((
+            puzzle.data.key(),
+            puzzle.stack.clone(),
+            puzzle.scramble.clone(),
+            puzzle.pieces.len(),
+        ));
+    }
+    ///the index of the piece of the solved state a piece of the puzzle belongs in, if it was found
+    pub fn home(&mut self, puzzle: &Puzzle, index: usize) -> Option<usize> {
+        self.update_homes(puzzle);
+        self.homes.get(index)?.1
+    }
+    ///whether each piece of the puzzle is selected by the filter
+    pub fn select(&mut self, puzzle: &Puzzle, filter: &PieceFilter) -> Vec<bool> {
+        if filter.needs_homes() {
+            self.orbits(puzzle);
+            self.update_homes(puzzle);
+        }
+        puzzle
+            .pieces
+            .iter()
+            .enumerate()
+            .map(|(i, piece)| {
+                if !filter.colors.is_empty() && !filter.colors.contains(&piece.piece.color) {
+                    return false;
+                }
+                if !filter.needs_homes() {
+                    return true;
+                }
+                let Some((home, index)) = self.homes.get(i) else {
+                    return false;
+                };
+                (filter.region.is_everywhere() || filter.region.contains_piece(home))
+                    && (filter.orbits.is_empty()
+                        || index
+                            .and_then(|x| self.orbits.get(x))
+                            .is_some_and(|x| filter.orbits.contains(x)))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,64 @@
+use approx_collections::ApproxEq;
+
+use crate::{
+    PRECISION,
+    complex::{complex_circle::Contains, point::Point},
+    puzzle::puzzle::Puzzle,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+///what is shown about a piece that is being inspected
+pub struct PieceInfo {
+    pub color: String, //the name of the color of the piece, or its hex code if the palette doesn't name it
+    pub arcs: usize,   //the number of arcs in the border of the piece
+    pub components: usize, //the number of separate parts the piece is made of
+    pub moved_by: Vec<String>, //the ids of the turns that move the piece, sorted
+    pub blocked: Vec<String>, //the ids of the turns the piece blocks (by crossing their circles), sorted
+    pub home: Option<usize>, //the index of the piece of the solved state the piece belongs in, if it was found
+    pub in_place: bool,      //whether the piece is where it belongs
+}
+
+impl Puzzle {
+    ///the index of the piece containing a point, if any. points on the border of a piece don't count
+    pub fn piece_at(&self, point: Point) -> Option<usize> {
+        self.pieces
+            .iter()
+            .position(|x| x.piece.shape.contains(point) == Contains::Inside)
+    }
+    ///information about a piece, given the index of the piece of the solved state it belongs in (see
+    ///FilterCache::home). None if there is no piece at the index
+    pub fn piece_info(&self, index: usize, home: Option<usize>) -> Option<PieceInfo> {
+        let piece = &self.pieces.get(index)?.piece;
+        let mut moved_by = Vec::new();
+        let mut blocked = Vec::new();
+        for (id, turn) in &self.turns {
+            match piece.in_circle(turn.turn.circle) {
+                Some(Contains::Inside) | Some(Contains::Border) => moved_by.push(id.clone()),
+                Some(Contains::Outside) => {}
+                None => blocked.push(id.clone()),
+            }
+        }
+        moved_by.sort();
+        blocked.sort();
+        let color = match self
+            .data
+            .used_colors()
+            .into_iter()
+            .find(|(_, c)| *c == piece.color)
+        {
+            Some((name, _)) => name,
+            None => String::from("uncolored"),
+        };
+        Some(PieceInfo {
+            color,
+            arcs: piece.shape.border.len(),
+            components: self.pieces[index].triangulations.len(),
+            moved_by,
+            blocked,
+            home,
+            in_place: home
+                .and_then(|x| self.solved_state.get(x))
+                .is_some_and(|x| x.piece.shape.approx_eq(&piece.shape, PRECISION)),
+        })
+    }
+}
@@ -1,8 +1,14 @@
+pub mod animation;
 pub mod color;
+pub mod filter;
+pub mod inspect;
 pub mod intern;
 pub mod piece;
 pub mod piece_shape;
 pub mod puzzle;
+pub mod raster;
+pub mod region;
 pub mod render_piece;
 pub mod solve_detection;
+pub mod svg;
 pub mod turn;
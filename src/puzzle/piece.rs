@@ -1,6 +1,8 @@
-
 use crate::{
-    complex::complex_circle::{Circle, ComplexCircle, Contains, OrientedCircle},
+    complex::{
+        complex_circle::{Circle, ComplexCircle, Contains, OrientedCircle},
+        symmetry::Isometry,
+    },
     puzzle::{color::Color, piece_shape::PieceShape},
 };
 
@@ -41,4 +43,11 @@ impl Piece {
     pub fn in_circle(&self, circle: ComplexCircle) -> Option<Contains> {
         self.shape.in_circle(circle)
     }
+    ///move the piece by an isometry, keeping its color
+    pub fn transformed(&self, isometry: &Isometry) -> Piece {
+        Piece {
+            shape: self.shape.transformed(isometry),
+            color: self.color,
+        }
+    }
 }
@@ -8,6 +8,7 @@ use crate::{
         arc::Arc,
         complex_circle::{Circle, Contains, OrientedCircle, inside_bounds},
         point::Point,
+        symmetry::Isometry,
     },
 };
 
@@ -22,6 +23,12 @@ impl PieceShape {
     pub fn contains(&self, point: Point) -> Contains {
         inside_bounds(&self.bounds, point)
     }
+    ///check if a shape contains the point at infinity, which only pieces of spherical puzzles can. since the shape is
+    ///the intersection of its bounding circles, it does exactly when they are all outside ones (the whole sphere has
+    ///none at all)
+    pub fn contains_infinity(&self) -> bool {
+        self.bounds.iter().all(|x| x.ori == Contains::Outside)
+    }
     ///cut a circle by a piece, used in cutting the border of the piece by a circle.
     ///throws out the resulting arcs lying outside the piece.
     ///returns two sets of arcs, with opposite orientations, again for cutting.
@@ -74,15 +81,26 @@ impl PieceShape {
                 outside.push(*arc);
             }
         }
+        let circle_arcs = self.cut_circle(circle);
+        //a circle inside the piece that doesn't meet its border cuts a hole out of it
+        let hole = circle_arcs.is_none() && self.contains(circle.right_point()) == Contains::Inside;
         //if all the arcs are inside or outside, no cut is made since one of the resulting pieces would be empty
-        if outside.is_empty() || inside.is_empty() {
+        if !hole && (outside.is_empty() || inside.is_empty()) {
             return None;
         }
         //cut the circle by the border and add the pieces to inside or outside
         //if the circle isnt cut, the piece might still be cut (if it was disconnected) so we just don't add any arcs
-        if let Some(circle_arcs) = self.cut_circle(circle) {
+        if let Some(circle_arcs) = circle_arcs {
             inside.extend(circle_arcs.0);
             outside.extend(circle_arcs.1);
+        } else if hole {
+            let arc = Arc {
+                circle,
+                start: circle.right_point(),
+                angle: 2. * PI,
+            };
+            inside.push(arc);
+            outside.push(arc.inverse());
         }
         Some((inside, outside))
     }
@@ -110,6 +128,32 @@ impl PieceShape {
             },
         ))
     }
+    ///move the shape by an isometry. the border of a reflected shape is reversed, so that it goes around the shape
+    ///the same way as before
+    pub fn transformed(&self, isometry: &Isometry) -> PieceShape {
+        let border = self.border.iter().map(|x| isometry.arc(*x));
+        PieceShape {
+            bounds: self
+                .bounds
+                .iter()
+                .map(|x| isometry.oriented_circle(*x))
+                .collect(),
+            border: if isometry.is_reflection() {
+                border.rev().map(|x| x.inverse()).collect()
+            } else {
+                border.collect()
+            },
+        }
+    }
+    ///whether a shape whose border is outside of a circle is outside of it. it isn't if the circle is inside the shape
+    ///without meeting its border, in which case the circle crosses it
+    fn in_circle_outside(&self, circle: Circle) -> Option<Contains> {
+        if self.contains(circle.right_point()) == Contains::Inside {
+            None
+        } else {
+            Some(Contains::Outside)
+        }
+    }
     ///detect if a shape is in a circle. Some(x) means that the shape is entirely x, None means that the shape crosses the border of the circle
     pub fn in_circle(&self, circle: Circle) -> Option<Contains> {
         let mut inside = None; //tracks whether the piece is inside the circle
@@ -127,10 +171,20 @@ impl PieceShape {
                 inside = Some(contained);
             }
         }
+        if self.contains_infinity() {
+            //a shape containing infinity is never inside a circle, so it crosses the circle if its border is inside it
+            return match inside {
+                Some(Contains::Inside) => None,
+                _ => self.in_circle_outside(circle),
+            };
+        }
         if inside.is_none_or(|x| x == Contains::Border) {
             //once all arcs have been iterated, if the value of inside was never set (i.e., all arcs lied on the border) return inside.
             return Some(Contains::Inside);
         }
+        if inside == Some(Contains::Outside) {
+            return self.in_circle_outside(circle);
+        }
         inside
     }
 }
@@ -1,8 +1,11 @@
 use crate::DETAIL;
+use crate::PRECISION;
+use crate::complex::rotation::Rotation;
+use crate::puzzle::color::Color;
 use crate::puzzle::piece::*;
 use crate::puzzle::render_piece::RenderPiece;
 use crate::puzzle::turn::*;
-use approx_collections::FloatPool;
+use approx_collections::{ApproxEqZero, FloatPool};
 use rand::SeedableRng;
 use rand::prelude::IteratorRandom;
 use std::collections::HashMap;
@@ -27,6 +30,21 @@ pub struct Puzzle {
     pub keybinds: HashMap<egui::Key, (String, isize)>,
     pub solved_state: Vec<RenderPiece>,
 }
+
+///the highest difficulty a puzzle can be given
+pub const MAX_DIFFICULTY: usize = 5;
+
+#[derive(Debug, Clone, Default)]
+///the optional information about a puzzle given to add_puzzle, shown in the puzzle info window and used by the catalog
+pub struct PuzzleMetadata {
+    pub description: String, //a short description of the puzzle, empty if none was given
+    pub tags: Vec<String>,   //tags for keybind overrides and the catalog
+    pub difficulty: Option<usize>, //how hard the puzzle is, from 1 to MAX_DIFFICULTY
+    pub created: String,     //when the puzzle was made, as YYYY-MM-DD, empty if unknown
+    pub inspired_by: Vec<String>, //the puzzles (or links to them) this puzzle is based on
+    pub aliases: Vec<String>, //other names the puzzle goes by
+}
+
 #[derive(Debug, Clone)]
 pub struct PuzzleData {
     pub name: String,
@@ -37,6 +55,33 @@ pub struct PuzzleData {
     pub intern: FloatPool,
     pub depth: usize,
     pub keybinds: HashMap<egui::Key, (String, isize)>,
+    pub palette: Vec<(String, Color)>, //the named colors declared by the definition
+    pub spherical: bool, //whether the puzzle is the stereographic projection of a sphere
+    pub metadata: PuzzleMetadata, //the information given to add_puzzle
+}
+
+impl PuzzleData {
+    ///the path the puzzle is stored under in the definition tree (its folder and name), used to save settings per puzzle
+    pub fn key(&self) -> String {
+        self.path
+            .with_file_name(&self.name)
+            .to_string_lossy()
+            .replace('\\', "/")
+    }
+    ///the distinct colors of the pieces of the puzzle, in the order they first appear, named by the palette if they are in it
+    pub fn used_colors(&self) -> Vec<(String, Color)> {
+        let mut colors: Vec<(String, Color)> = Vec::new();
+        for piece in &self.pieces {
+            if colors.iter().all(|(_, c)| *c != piece.color) {
+                let name = match self.palette.iter().find(|(_, c)| *c == piece.color) {
+                    Some((name, _)) => name.clone(),
+                    None => piece.color.to_hex().unwrap_or(String::from("uncolored")),
+                };
+                colors.push((name, piece.color));
+            }
+        }
+        colors
+    }
 }
 
 impl Puzzle {
@@ -115,6 +160,48 @@ impl Puzzle {
         self.stack.push((id.to_string(), mult));
         Ok(true)
     }
+    ///show a turn partway through being dragged, with the pieces in its circle rotated by the swept angle
+    pub fn preview_drag(&mut self, id: &str, swept: f64) {
+        if let Some(turn) = self.turns.get(id) {
+            self.animation_offset = Some(Turn {
+                rot: Rotation::from_angle(swept),
+                ..turn.turn
+            });
+            self.anim_left = 1.0;
+        }
+    }
+    ///finish a turn done by dragging, snapping the swept angle to the nearest multiple of the turn.
+    ///the rest of the rotation is animated from where the drag was released.
+    ///if the turn was completed, returns Ok(true).
+    ///if the angle snapped to no turn or the turn was bandaged, returns Ok(false) and animates back
+    pub fn finish_drag(&mut self, id: &str, swept: f64, cut: bool) -> Result<bool, String> {
+        let turn = *self
+            .turns
+            .get(id)
+            .ok_or("No turn found with ID!".to_string())?;
+        let angle = turn.turn.rot.angle();
+        let mult = if angle.approx_eq_zero(PRECISION) {
+            0
+        } else {
+            (swept / angle).round() as isize
+        };
+        let residual = swept - (mult as f64 * angle); //how far the drag was from the snapped turn
+        //use the smallest multiple that does the same turn, so that the stack stays readable
+        let order = turn.order as isize;
+        let mult = if order == 0 {
+            mult
+        } else {
+            let m = mult.rem_euclid(order);
+            if m > order / 2 { m - order } else { m }
+        };
+        let turned = mult != 0 && self.turn_id(id, cut, mult)?;
+        self.animation_offset = Some(Turn {
+            rot: Rotation::from_angle(if turned { residual } else { swept }),
+            ..turn.turn
+        });
+        self.anim_left = 1.0;
+        Ok(turned)
+    }
     ///undoes the last turn.
     ///Ok(true) means that the move was undone successfully
     ///Ok(false) means that the stack was empty
@@ -0,0 +1,288 @@
+use crate::{
+    complex::point::Point,
+    puzzle::{
+        color::{Color, ColorScheme},
+        puzzle::Puzzle,
+        render_piece::{RenderPiece, bounds},
+    },
+};
+
+///how many samples are taken along each side of a pixel, to smooth the edges
+const SUPERSAMPLING: u32 = 3;
+///the color drawn for pieces that were never colored, the same as they are drawn in the app
+const NONE_COLOR: [u8; 4] = [160, 160, 160, 255];
+///the color of the outlines
+const OUTLINE_COLOR: [u8; 4] = [0, 0, 0, 255];
+
+#[derive(Debug, Clone, Copy)]
+///options for rasterizing a puzzle
+pub struct RasterOptions {
+    pub size: u32,          //the length of the longer side of the image, in pixels
+    pub outline_width: f64, //the width of the outlines, in pixels
+    pub solved: bool,       //whether to draw the solved state instead of the current one
+}
+
+impl Default for RasterOptions {
+    fn default() -> Self {
+        Self {
+            size: 256,
+            outline_width: 1.0,
+            solved: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+///an image stored as rgba pixels (not premultiplied), row by row from the top left
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<[u8; 4]>,
+}
+
+impl Image {
+    ///make a fully transparent image
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![[0; 4]; (width * height) as usize],
+        }
+    }
+    ///the pixel at a position
+    pub fn get(&self, x: u32, y: u32) -> [u8; 4] {
+        self.pixels[(y * self.width + x) as usize]
+    }
+    ///draw a color over a pixel, blending by its alpha. positions outside the image are ignored
+    fn blend(&mut self, x: i64, y: i64, color: [u8; 4]) {
+        if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+            return;
+        }
+        let pixel = &mut self.pixels[(y * self.width as i64 + x) as usize];
+        let (src_a, dst_a) = (color[3] as f64 / 255.0, pixel[3] as f64 / 255.0);
+        let out_a = src_a + dst_a * (1.0 - src_a);
+        if out_a == 0.0 {
+            return;
+        }
+        for (channel, src) in pixel.iter_mut().zip(color).take(3) {
+            *channel = ((src as f64 * src_a + *channel as f64 * dst_a * (1.0 - src_a)) / out_a)
+                .round() as u8;
+        }
+        pixel[3] = (out_a * 255.0).round() as u8;
+    }
+    ///fill a triangle, given in pixel coordinates. a pixel is filled if its center is inside the triangle
+    fn fill_triangle(&mut self, triangle: [(f64, f64); 3], color: [u8; 4]) {
+        let [a, b, c] = triangle;
+        let side = |p: (f64, f64), q: (f64, f64), r: (f64, f64)| {
+            (q.0 - p.0) * (r.1 - p.1) - (q.1 - p.1) * (r.0 - p.0)
+        };
+        let (min_x, max_x) = (a.0.min(b.0).min(c.0), a.0.max(b.0).max(c.0));
+        let (min_y, max_y) = (a.1.min(b.1).min(c.1), a.1.max(b.1).max(c.1));
+        for y in min_y.floor().max(0.0) as i64..=max_y.ceil().min(self.height as f64) as i64 {
+            for x in min_x.floor().max(0.0) as i64..=max_x.ceil().min(self.width as f64) as i64 {
+                let p = (x as f64 + 0.5, y as f64 + 0.5);
+                let (d1, d2, d3) = (side(a, b, p), side(b, c, p), side(c, a, p));
+                if !((d1 < 0.0 || d2 < 0.0 || d3 < 0.0) && (d1 > 0.0 || d2 > 0.0 || d3 > 0.0)) {
+                    self.blend(x, y, color);
+                }
+            }
+        }
+    }
+    ///draw a line segment of a width, given in pixel coordinates, with round ends
+    fn draw_segment(&mut self, a: (f64, f64), b: (f64, f64), width: f64, color: [u8; 4]) {
+        let r = width / 2.0;
+        let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+        let len_sq = dx * dx + dy * dy;
+        for y in (a.1.min(b.1) - r).floor().max(0.0) as i64
+            ..=(a.1.max(b.1) + r).ceil().min(self.height as f64) as i64
+        {
+            for x in (a.0.min(b.0) - r).floor().max(0.0) as i64
+                ..=(a.0.max(b.0) + r).ceil().min(self.width as f64) as i64
+            {
+                let p = (x as f64 + 0.5, y as f64 + 0.5);
+                //the closest point of the segment to the center of the pixel
+                let t = if len_sq == 0.0 {
+                    0.0
+                } else {
+                    (((p.0 - a.0) * dx + (p.1 - a.1) * dy) / len_sq).clamp(0.0, 1.0)
+                };
+                let (cx, cy) = (a.0 + t * dx - p.0, a.1 + t * dy - p.1);
+                if cx * cx + cy * cy <= r * r {
+                    self.blend(x, y, color);
+                }
+            }
+        }
+    }
+    ///shrink the image by a whole factor, averaging each block of pixels (weighted by alpha, so edges don't darken)
+    fn downsampled(&self, factor: u32) -> Self {
+        let mut image = Self::new(self.width / factor, self.height / factor);
+        for y in 0..image.height {
+            for x in 0..image.width {
+                let mut sums = [0.0; 4];
+                for j in 0..factor {
+                    for i in 0..factor {
+                        let pixel = self.get(x * factor + i, y * factor + j);
+                        let a = pixel[3] as f64;
+                        for (sum, channel) in sums.iter_mut().zip(pixel).take(3) {
+                            *sum += channel as f64 * a;
+                        }
+                        sums[3] += a;
+                    }
+                }
+                if sums[3] > 0.0 {
+                    image.pixels[(y * image.width + x) as usize] = [
+                        (sums[0] / sums[3]).round() as u8,
+                        (sums[1] / sums[3]).round() as u8,
+                        (sums[2] / sums[3]).round() as u8,
+                        (sums[3] / (factor * factor) as f64).round() as u8,
+                    ];
+                }
+            }
+        }
+        image
+    }
+    ///the raw bytes of the pixels, four per pixel
+    pub fn as_bytes(&self) -> &[u8] {
+        self.pixels.as_flattened()
+    }
+    ///encode the image as a png file
+    pub fn to_png(&self) -> Result<Vec<u8>, String> {
+        let mut bytes = Vec::new();
+        let mut encoder = png::Encoder::new(&mut bytes, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(|err| err.to_string())?;
+        writer
+            .write_image_data(self.as_bytes())
+            .map_err(|err| err.to_string())?;
+        writer.finish().map_err(|err| err.to_string())?;
+        Ok(bytes)
+    }
+    ///decode a png file. only 8 bit rgba images (like the ones written by to_png) are supported
+    pub fn from_png(bytes: &[u8]) -> Result<Self, String> {
+        let mut reader = png::Decoder::new(std::io::Cursor::new(bytes))
+            .read_info()
+            .map_err(|err| err.to_string())?;
+        let size = reader
+            .output_buffer_size()
+            .ok_or("the png is too large to decode")?;
+        let mut data = vec![0; size];
+        let info = reader
+            .next_frame(&mut data)
+            .map_err(|err| err.to_string())?;
+        if info.color_type != png::ColorType::Rgba || info.bit_depth != png::BitDepth::Eight {
+            return Err(String::from("only 8 bit rgba pngs are supported"));
+        }
+        Ok(Self {
+            width: info.width,
+            height: info.height,
+            pixels: data[..info.buffer_size()]
+                .chunks_exact(4)
+                .map(|x| [x[0], x[1], x[2], x[3]])
+                .collect(),
+        })
+    }
+}
+
+///turns puzzle coordinates into pixel coordinates, which are scaled and have y pointing down
+struct PixelTransform {
+    min_x: f64,
+    max_y: f64,
+    scale: f64,
+    margin: f64,
+}
+
+impl PixelTransform {
+    fn point(&self, point: Point) -> (f64, f64) {
+        (
+            (point.0.re - self.min_x) * self.scale + self.margin,
+            (self.max_y - point.0.im) * self.scale + self.margin,
+        )
+    }
+}
+
+///the rgba values a color is drawn with
+fn rgba(color: Color) -> [u8; 4] {
+    match color {
+        Color::Rgba(x) => x,
+        Color::None => NONE_COLOR,
+    }
+}
+
+impl Puzzle {
+    ///the pieces as they are drawn, partway through the animation in progress
+    pub fn animated_pieces(&self) -> Vec<RenderPiece> {
+        let offset = self
+            .animation_offset
+            .map(|off| off.mult(self.anim_left as f64));
+        self.pieces.iter().map(|x| x.offset_by(offset)).collect()
+    }
+    ///draw the puzzle into an image on the cpu, from the triangulations of its pieces, with the colors replaced by the
+    ///color scheme. the background is transparent, and markings are not drawn
+    pub fn rasterize(&self, options: &RasterOptions, scheme: &ColorScheme) -> Image {
+        let pieces = if options.solved {
+            self.solved_state.clone()
+        } else {
+            self.animated_pieces()
+        };
+        rasterize_pieces(&pieces, bounds(&pieces), options, scheme)
+    }
+}
+
+///draw pieces into an image, fitting the area given by the bounds (as `[min_x, min_y, max_x, max_y]`) to the size.
+///pieces outside of the bounds are cut off
+pub fn rasterize_pieces(
+    pieces: &[RenderPiece],
+    bounds: [f64; 4],
+    options: &RasterOptions,
+    scheme: &ColorScheme,
+) -> Image {
+    let [min_x, min_y, max_x, max_y] = bounds;
+    let (size, margin) = (
+        (options.size.max(1) * SUPERSAMPLING) as f64,
+        options.outline_width * SUPERSAMPLING as f64,
+    );
+    let scale = (size - 2.0 * margin).max(1.0) / (max_x - min_x).max(max_y - min_y);
+    let transform = PixelTransform {
+        min_x,
+        max_y,
+        scale,
+        margin,
+    };
+    //round the sides to whole pixels of the final image
+    let side = |extent: f64| {
+        ((extent * scale + 2.0 * margin) / SUPERSAMPLING as f64).round() as u32 * SUPERSAMPLING
+    };
+    let mut image = Image::new(side(max_x - min_x), side(max_y - min_y));
+    for piece in pieces {
+        draw_piece(&mut image, piece, &transform, scheme);
+    }
+    if options.outline_width > 0.0 {
+        for piece in pieces {
+            for arc in piece.triangulations.iter().flat_map(|x| &x.border) {
+                for pair in arc.windows(2) {
+                    image.draw_segment(
+                        transform.point(pair[0]),
+                        transform.point(pair[1]),
+                        margin,
+                        OUTLINE_COLOR,
+                    );
+                }
+            }
+        }
+    }
+    image.downsampled(SUPERSAMPLING)
+}
+
+///fill the triangles of a piece with its color
+fn draw_piece(
+    image: &mut Image,
+    piece: &RenderPiece,
+    transform: &PixelTransform,
+    scheme: &ColorScheme,
+) {
+    let color = rgba(scheme.apply(piece.piece.color));
+    for triangle in piece.triangulations.iter().flat_map(|x| &x.inside) {
+        image.fill_triangle(triangle.map(|x| transform.point(x)), color);
+    }
+}
@@ -0,0 +1,123 @@
+use approx_collections::ApproxEq;
+
+use crate::{
+    PRECISION,
+    complex::{
+        complex_circle::{Contains, OrientedCircle, inside_bounds},
+        point::Point,
+        symmetry::Isometry,
+    },
+    puzzle::piece::Piece,
+};
+
+#[derive(Debug, Clone)]
+///a region of the plane, stored as a union of parts, each of which is the intersection of some oriented circles (a CCP
+///representation). every set operation gives another region of this form. a piece is only in the region if it lies
+///in a single part, so a piece straddling the seam between two parts is not, like a piece in `a | b` that is partly
+///outside of `a` and partly outside of `b`
+pub struct Region(pub Vec<Vec<OrientedCircle>>);
+
+impl Default for Region {
+    fn default() -> Self {
+        Self::everywhere()
+    }
+}
+
+impl From<Vec<OrientedCircle>> for Region {
+    ///the intersection of the circles
+    fn from(circles: Vec<OrientedCircle>) -> Self {
+        Self(vec![circles])
+    }
+}
+
+impl From<OrientedCircle> for Region {
+    fn from(circle: OrientedCircle) -> Self {
+        Self(vec![vec![circle]])
+    }
+}
+
+impl Region {
+    ///the whole plane (a single part with no circles)
+    pub fn everywhere() -> Self {
+        Self(vec![Vec::new()])
+    }
+    ///the empty region (no parts)
+    pub fn nowhere() -> Self {
+        Self(Vec::new())
+    }
+    ///whether the region is trivially the whole plane
+    pub fn is_everywhere(&self) -> bool {
+        self.0.iter().any(|x| x.is_empty())
+    }
+    ///the points in both regions
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self(
+            self.0
+                .iter()
+                .flat_map(|a| {
+                    other
+                        .0
+                        .iter()
+                        .map(move |b| a.iter().chain(b).copied().collect())
+                })
+                .collect(),
+        )
+        .simplified()
+    }
+    ///the points in either region
+    pub fn union(&self, other: &Self) -> Self {
+        Self(self.0.iter().chain(&other.0).cloned().collect()).simplified()
+    }
+    ///the points not in the region. by de morgan's laws, this is the intersection over the parts of the union of
+    ///their flipped circles
+    pub fn complement(&self) -> Self {
+        self.0.iter().fold(Self::everywhere(), |region, part| {
+            region.intersection(&Self(part.iter().map(|x| vec![-*x]).collect()))
+        })
+    }
+    ///the points in this region but not the other
+    pub fn difference(&self, other: &Self) -> Self {
+        self.intersection(&other.complement())
+    }
+    ///move the region by an isometry
+    pub fn transformed(&self, isometry: &Isometry) -> Self {
+        Self(
+            self.0
+                .iter()
+                .map(|part| part.iter().map(|x| isometry.oriented_circle(*x)).collect())
+                .collect(),
+        )
+    }
+    ///whether a point is properly inside the region
+    pub fn contains(&self, point: Point) -> bool {
+        self.0
+            .iter()
+            .any(|x| inside_bounds(x, point) == Contains::Inside)
+    }
+    ///whether a piece lies in the region, that is, in one of its parts (see Piece::in_region). pieces that are only
+    ///covered by several parts together are not counted
+    pub fn contains_piece(&self, piece: &Piece) -> bool {
+        self.0.iter().any(|x| piece.in_region(x))
+    }
+    ///remove repeated circles from each part, and parts that are empty because they are both inside and outside of a
+    ///circle
+    fn simplified(self) -> Self {
+        Self(
+            self.0
+                .into_iter()
+                .filter_map(|part| {
+                    let mut circles: Vec<OrientedCircle> = Vec::new();
+                    for circle in part {
+                        if circles.iter().any(|x| x.approx_eq(&-circle, PRECISION)) {
+                            return None;
+                        }
+                        if !circles.iter().any(|x| x.approx_eq(&circle, PRECISION)) {
+                            circles.push(circle);
+                        }
+                    }
+                    Some(circles)
+                })
+                .collect(),
+        )
+    }
+}
@@ -4,7 +4,7 @@ use approx_collections::ApproxEq;
 
 use crate::{
     PRECISION,
-    complex::{arc::Arc, c64::C64, complex_circle::Contains, point::Point},
+    complex::{arc::Arc, c64::C64, complex_circle::Contains, point::Point, sphere::SpherePoint},
     puzzle::{piece::Piece, piece_shape::PieceShape, turn::Turn},
 };
 
@@ -14,6 +14,17 @@ const DETAIL_FACTOR: f64 = 3.0;
 ///leniency for degenerate triangles
 const LENIENCY: f64 = 0.01;
 
+///how finely arcs are sampled to find the bounds of pieces
+const BOUNDS_DETAIL: f64 = 50.0;
+
+///how far away pieces containing infinity are cut off when they are triangulated. far enough to never be on the
+///screen, and to leave a gap around the south pole too small to see in the orthographic view
+const FAR: f64 = 1e4;
+
+///slabs thinner than this are skipped when triangulating by slabs, since they only come from the ends of arcs not
+///quite meeting
+const MIN_SLAB: f64 = 1e-9;
+
 ///component for rendering
 pub struct Component {
     pub shape: Vec<Arc>,
@@ -24,6 +35,7 @@ pub struct Component {
 pub struct Triangulation {
     pub inside: Vec<[Point; 3]>,
     pub border: Vec<TriangulatedArc>,
+    pub center: Point, //the estimated barycenter of the component, where glyphs are drawn
 }
 
 ///triangulated arc (duh)
@@ -84,8 +96,20 @@ impl Component {
         Triangulation {
             inside: triangles,
             border: borders,
+            center: bary,
         }
     }
+    ///the signed area of the component, positive if it goes counterclockwise and negative if it goes clockwise around a
+    ///hole. each arc adds half the cross product of its circle's center with its chord, and half its angle times r^2
+    pub fn area(&self) -> f64 {
+        self.shape
+            .iter()
+            .map(|arc| {
+                let (center, chord) = (arc.circle.center.0, (arc.end() - arc.start).0);
+                (center.re * chord.im - center.im * chord.re + arc.circle.r_sq * arc.angle) / 2.0
+            })
+            .sum()
+    }
     ///estimate the barycenter by averaging the midpoints of all the arcs
     pub fn barycenter(&self) -> Point {
         let mut center = Point(C64 { re: 0.0, im: 0.0 });
@@ -121,6 +145,15 @@ impl PieceShape {
                 let mut curr_comp = vec![arc];
                 comps.push(Component {
                     shape: loop {
+                        //a component can be a single arc, going all the way around a circle
+                        if curr_comp
+                            .last()
+                            .unwrap()
+                            .end()
+                            .approx_eq(&curr_comp[0].start, PRECISION)
+                        {
+                            break curr_comp;
+                        }
                         if arcs.is_empty() {
                             return vec![Component {
                                 shape: self.border.clone(),
@@ -130,9 +163,6 @@ impl PieceShape {
                             find_next_arc(curr_comp.last().unwrap().end(), &mut arcs)
                         {
                             curr_comp.push(next);
-                            if next.end().approx_eq(&curr_comp[0].start, PRECISION) {
-                                break curr_comp;
-                            }
                         } else {
                             return vec![Component {
                                 shape: self.border.clone(),
@@ -145,23 +175,122 @@ impl PieceShape {
             }
         }
     }
+    ///triangulate the whole shape by cutting it into slabs between the heights of the points of its border, and the
+    ///slabs into trapezoids between the places the border crosses them, alternating between inside and outside. unlike
+    ///the components, this works for shapes with holes and shapes containing infinity, which are cut off far away
+    pub fn triangulate_slabs(&self, detail: f64) -> Triangulation {
+        let mut edges = self
+            .border
+            .iter()
+            .flat_map(|x| {
+                x.get_polygon(detail)
+                    .windows(2)
+                    .map(|y| [y[0], y[1]])
+                    .collect::<Vec<[Point; 2]>>()
+            })
+            .collect::<Vec<[Point; 2]>>();
+        let point = |re, im| Point(C64 { re, im });
+        if self.contains_infinity() {
+            //a far away square around everything, so that the shape is the inside of it minus the holes
+            let corners =
+                [(FAR, FAR), (-FAR, FAR), (-FAR, -FAR), (FAR, -FAR)].map(|(x, y)| point(x, y));
+            edges.extend((0..4).map(|i| [corners[i], corners[(i + 1) % 4]]));
+        }
+        let mut heights = edges.iter().flatten().map(|x| x.0.im).collect::<Vec<f64>>();
+        heights.sort_by(f64::total_cmp);
+        heights.dedup();
+        let x_at = |[a, b]: [Point; 2], y: f64| {
+            a.0.re + (y - a.0.im) * (b.0.re - a.0.re) / (b.0.im - a.0.im)
+        };
+        let mut triangles = Vec::new();
+        for slab in heights.windows(2) {
+            let (bottom, top) = (slab[0], slab[1]);
+            if top - bottom < MIN_SLAB {
+                continue;
+            }
+            //every edge crossing the slab goes all the way across it, since the slabs are cut at every endpoint
+            let mut crossings = edges
+                .iter()
+                .filter(|[a, b]| a.0.im.min(b.0.im) <= bottom && a.0.im.max(b.0.im) >= top)
+                .map(|x| (x_at(*x, bottom), x_at(*x, top)))
+                .collect::<Vec<(f64, f64)>>();
+            crossings.sort_by(|a, b| (a.0 + a.1).total_cmp(&(b.0 + b.1)));
+            for pair in crossings.chunks_exact(2) {
+                let [(left_bottom, left_top), (right_bottom, right_top)] = [pair[0], pair[1]];
+                let (a, b) = (point(left_bottom, bottom), point(right_bottom, bottom));
+                let (c, d) = (point(right_top, top), point(left_top, top));
+                triangles.push([a, b, c]);
+                triangles.push([a, c, d]);
+            }
+        }
+        let bary = Component {
+            shape: self.border.clone(),
+        }
+        .barycenter();
+        Triangulation {
+            inside: triangles,
+            border: self
+                .border
+                .iter()
+                .map(|x| x.get_polygon(detail * DETAIL_FACTOR))
+                .collect(),
+            center: if self.contains_infinity() && !self.border.is_empty() {
+                //across the sphere from the middle of the border, which is roughly the middle of the shape
+                match SpherePoint::from_point(bary).antipode().to_point() {
+                    Some(x) if x.0.mag() < FAR => x,
+                    _ => point(FAR, 0.0),
+                }
+            } else {
+                bary
+            },
+        }
+    }
 }
 
 impl Piece {
-    ///triangulate the piece to get a RenderPiece
+    ///triangulate the piece to get a RenderPiece. a piece with holes (components going clockwise) or containing
+    ///infinity can't be triangulated component by component, so it is triangulated by slabs instead
     pub fn triangulate(self, detail: f64) -> RenderPiece {
+        let components = self.shape.calculate_components();
         RenderPiece {
-            triangulations: self
-                .shape
-                .calculate_components()
-                .iter()
-                .map(|x| x.triangulate_component(detail))
-                .collect(),
+            triangulations: if self.shape.contains_infinity()
+                || components.iter().any(|x| x.area() < 0.0)
+            {
+                vec![self.shape.triangulate_slabs(detail)]
+            } else {
+                components
+                    .iter()
+                    .map(|x| x.triangulate_component(detail))
+                    .collect()
+            },
             piece: self,
         }
     }
 }
 
+///the bounding box of a list of pieces, as `[min_x, min_y, max_x, max_y]`, found by sampling every arc.
+///pieces without any arcs (like the whole sphere) give the box from -1 to 1
+pub fn bounds(pieces: &[RenderPiece]) -> [f64; 4] {
+    if pieces.iter().all(|x| x.piece.shape.border.is_empty()) {
+        return [-1.0, -1.0, 1.0, 1.0];
+    }
+    let mut bounds = [
+        f64::INFINITY,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        f64::NEG_INFINITY,
+    ];
+    for arc in pieces.iter().flat_map(|x| &x.piece.shape.border) {
+        for point in arc.get_polygon(BOUNDS_DETAIL) {
+            bounds[0] = bounds[0].min(point.0.re);
+            bounds[1] = bounds[1].min(point.0.im);
+            bounds[2] = bounds[2].max(point.0.re);
+            bounds[3] = bounds[3].max(point.0.im);
+        }
+    }
+    bounds
+}
+
 ///rotate a list of triangulations according to a turn
 pub fn rot_triangulations(tri: Vec<Triangulation>, turn: Turn) -> Vec<Triangulation> {
     tri.iter()
@@ -176,16 +305,29 @@ pub fn rot_triangulations(tri: Vec<Triangulation>, turn: Turn) -> Vec<Triangulat
                 .iter()
                 .map(|y| y.iter().map(|z| turn.rot_point(*z)).collect())
                 .collect(),
+            center: turn.rot_point(x.center),
         })
         .collect()
 }
 
+impl RenderPiece {
+    ///the piece as it is drawn partway through an animation: turned by the offset if it is inside the offset's circle
+    pub fn offset_by(&self, offset: Option<Turn>) -> RenderPiece {
+        match offset {
+            Some(twist) if self.piece.shape.in_circle(twist.circle) == Some(Contains::Inside) => {
+                twist.turn_render_piece(self).unwrap_or(self.clone())
+            }
+            _ => self.clone(),
+        }
+    }
+}
+
 impl Turn {
     ///equivalent to turn_piece
     pub fn turn_render_piece(&self, piece: &RenderPiece) -> Option<RenderPiece> {
         let (shape, triangles) = if piece.piece.shape.in_circle(self.circle)? != Contains::Outside {
             (
-                self.rot_pieceshape(&piece.piece.shape),
+                self.rot_pieceshape(&piece.piece.shape)?,
                 rot_triangulations(piece.triangulations.clone(), *self),
             )
         } else {
@@ -0,0 +1,140 @@
+use std::f64::consts::PI;
+
+use crate::{
+    complex::{arc::Arc, point::Point},
+    puzzle::{
+        color::ColorScheme,
+        puzzle::Puzzle,
+        render_piece::{RenderPiece, bounds},
+    },
+};
+
+///the color written for pieces that were never colored, the same as they are drawn in the app
+const NONE_COLOR: &str = "#a0a0a0";
+
+#[derive(Debug, Clone, Copy)]
+///options for writing a puzzle as an svg
+pub struct SvgOptions {
+    pub size: f64,          //the length of the longer side of the image, in pixels
+    pub outline_width: f64, //the width of the outlines, in pixels
+    pub solved: bool,       //whether to write the solved state instead of the current one
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            size: 1000.0,
+            outline_width: 2.0,
+            solved: false,
+        }
+    }
+}
+
+///turns puzzle coordinates into svg coordinates, which are scaled and have y pointing down
+struct SvgTransform {
+    min_x: f64,
+    max_y: f64,
+    scale: f64,
+    margin: f64,
+}
+
+impl SvgTransform {
+    fn point(&self, point: Point) -> (f64, f64) {
+        (
+            (point.0.re - self.min_x) * self.scale + self.margin,
+            (self.max_y - point.0.im) * self.scale + self.margin,
+        )
+    }
+}
+
+impl Puzzle {
+    ///write the puzzle as an svg document. every piece is one path made of the exact arcs of its border, filled with its color
+    ///(after the color scheme). the animation in progress is ignored
+    pub fn to_svg(&self, options: &SvgOptions, scheme: &ColorScheme) -> String {
+        let pieces = if options.solved {
+            &self.solved_state
+        } else {
+            &self.pieces
+        };
+        let [min_x, min_y, max_x, max_y] = bounds(pieces);
+        let scale = options.size / (max_x - min_x).max(max_y - min_y);
+        let transform = SvgTransform {
+            min_x,
+            max_y,
+            scale,
+            margin: options.outline_width,
+        };
+        let (width, height) = (
+            (max_x - min_x) * scale + 2.0 * transform.margin,
+            (max_y - min_y) * scale + 2.0 * transform.margin,
+        );
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width:.2}\" height=\"{height:.2}\" viewBox=\"0 0 {width:.2} {height:.2}\">\n"
+        );
+        svg += &format!(
+            "<g stroke=\"black\" stroke-width=\"{}\" stroke-linejoin=\"round\" fill-rule=\"evenodd\">\n",
+            options.outline_width
+        );
+        for piece in pieces {
+            svg += &piece_path(piece, &transform, (width, height), scheme);
+        }
+        svg += "</g>\n</svg>\n";
+        svg
+    }
+}
+
+///write a piece as an svg path element, with one closed subpath for each component. a piece containing infinity (on a
+///spherical puzzle) starts with a rectangle around the whole image (just outside of it, so its outline isn't drawn),
+///which the even-odd rule cuts the components out of
+fn piece_path(
+    piece: &RenderPiece,
+    transform: &SvgTransform,
+    (width, height): (f64, f64),
+    scheme: &ColorScheme,
+) -> String {
+    let mut data = String::new();
+    if piece.piece.shape.contains_infinity() {
+        let (min, max_x, max_y) = (
+            -transform.margin,
+            width + transform.margin,
+            height + transform.margin,
+        );
+        data += &format!("M {min:.3} {min:.3} H {max_x:.3} V {max_y:.3} H {min:.3} Z ");
+    }
+    for component in piece.piece.shape.calculate_components() {
+        let Some(first) = component.shape.first() else {
+            continue;
+        };
+        let (x, y) = transform.point(first.start);
+        data += &format!("M {x:.3} {y:.3} ");
+        for arc in &component.shape {
+            data += &arc_segments(arc, transform);
+        }
+        data += "Z ";
+    }
+    let fill = scheme
+        .apply(piece.piece.color)
+        .to_hex()
+        .unwrap_or(String::from(NONE_COLOR));
+    format!("<path fill=\"{fill}\" d=\"{}\"/>\n", data.trim_end())
+}
+
+///write an arc as svg arc commands. arcs over half a circle are split, so that full circles can be written
+fn arc_segments(arc: &Arc, transform: &SvgTransform) -> String {
+    if arc.angle.abs() > PI {
+        let half = Arc {
+            angle: arc.angle / 2.0,
+            ..*arc
+        };
+        let rest = Arc {
+            start: half.end(),
+            ..half
+        };
+        return arc_segments(&half, transform) + &arc_segments(&rest, transform);
+    }
+    let r = arc.circle.r() * transform.scale;
+    let (x, y) = transform.point(arc.end());
+    //svg's y axis points down, so a counterclockwise arc goes in svg's negative angle direction (sweep 0)
+    let sweep = (arc.angle < 0.0) as u8;
+    format!("A {r:.3} {r:.3} 0 0 {sweep} {x:.3} {y:.3} ")
+}
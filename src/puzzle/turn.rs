@@ -1,89 +1,171 @@
+use std::f64::consts::PI;
+
 use crate::{
     complex::{
         arc::Arc,
         c64::Scalar,
         complex_circle::{Circle, Contains, OrientedCircle},
+        mobius::Mobius,
         point::Point,
         rotation::Rotation,
+        sphere::{SpherePoint, cap},
+        symmetry::Isometry,
     },
     puzzle::{piece::Piece, piece_shape::PieceShape},
 };
 
 #[derive(Clone, Debug, Copy)]
 ///turn of a certain angle around a circle. only points within the circle should be affected.
+///usually a rotation about the circle's center, but a turn with a pivot is the elliptic möbius transformation around
+///the pivot instead, which maps the circle to itself without being rigid
 pub struct Turn {
     pub circle: Circle,
-    pub rot: Rotation, //rotation is stored as a mag-1 complex number
+    pub rot: Rotation,        //rotation is stored as a mag-1 complex number
+    pub pivot: Option<Point>, //the point inside the circle a möbius turn rotates around, or None for a rotation
 }
 
 impl Turn {
+    ///the rotation of the sphere around an axis, turning the cap around it of an angular radius (see sphere::cap). in
+    ///the projection this is the möbius turn around the projection of the axis. a cap containing the south pole can't
+    ///be turned in the projection, so the rest of the sphere is turned the other way instead, which is the same move
+    ///up to a rotation of the whole sphere. returns None if the edge of the cap goes through the south pole
+    pub fn spherical(axis: SpherePoint, radius: Scalar, rot: Rotation) -> Option<Self> {
+        let cap = cap(axis, radius)?;
+        if cap.ori != Contains::Inside {
+            //turning the cap around the opposite axis by the same rotation turns it the other way
+            return Self::spherical(axis.antipode(), PI - radius, rot);
+        }
+        Some(Self {
+            circle: cap.circ,
+            rot,
+            pivot: Some(axis.to_point()?),
+        })
+    }
     ///take the inverse of a turn, which is around the same circle but with flipped sign on the angle
     pub fn inverse(&self) -> Self {
         Self {
-            circle: self.circle,
             rot: self.rot.conj(),
+            ..*self
         }
     }
     ///multiply a turn by an integer.
     pub fn mult(&self, mult: Scalar) -> Self {
         Self {
-            circle: self.circle,
             rot: (Rotation::from_angle(self.rot.angle() * (mult as Scalar))), //multiply the angle by the scalar and recalculate the number
+            ..*self
+        }
+    }
+    ///move the turn by an isometry. reflections reverse the direction of the turn, so that turning and then moving
+    ///the pieces is the same as moving them and then doing the moved turn
+    pub fn transformed(&self, isometry: &Isometry) -> Self {
+        Self {
+            circle: isometry.circle(self.circle),
+            rot: if isometry.is_reflection() {
+                self.rot.conj()
+            } else {
+                self.rot
+            },
+            pivot: self.pivot.map(|x| isometry.point(x)),
+        }
+    }
+    ///the point the turn rotates around: its pivot, or the center of its circle
+    pub fn center(&self) -> Point {
+        self.pivot.unwrap_or(self.circle.center)
+    }
+    ///the angle of a point around the turn, which turning adds the turn's angle to. for a möbius turn, this is the
+    ///angle of (z - pivot) / (z - q), where q is the inverse of the pivot in the circle
+    pub fn angle_of(&self, point: Point) -> Scalar {
+        match self.pivot.and_then(|x| Some((x, self.circle.invert(x)?))) {
+            Some((p, q)) => ((point - p).0 / (point - q).0).angle(),
+            None => (point - self.circle.center).angle(),
         }
     }
+    ///the möbius transformation of a turn with a pivot. None for rotations (and pivots outside of the circle)
+    pub fn mobius(&self) -> Option<Mobius> {
+        Mobius::elliptic(self.circle, self.pivot?, self.rot)
+    }
     ///rotate a point according to the turn. does not care whether the point is in/out of the circle
     pub fn rot_point(&self, point: Point) -> Point {
-        self.circle.center + (self.rot * (point - self.circle.center))
+        match self.mobius() {
+            Some(mobius) => mobius.point(point),
+            None => self.circle.center + (self.rot * (point - self.circle.center)),
+        }
+    }
+    ///rotate a circle according to the turn. does not care whether the circle is in/out of the circle.
+    ///returns None for a möbius turn and a circle through its pole, since the image would be a line
+    pub fn rot_circle(&self, circle: Circle) -> Option<Circle> {
+        match self.mobius() {
+            Some(mobius) => mobius.circle(circle),
+            None => Some(Circle {
+                center: self.rot_point(circle.center),
+                r_sq: circle.r_sq,
+            }),
+        }
     }
-    ///rotate a circle according to the turn. does not care whether the circle is in/out of the circle
-    pub fn rot_circle(&self, circle: Circle) -> Circle {
-        Circle {
-            center: self.rot_point(circle.center),
-            r_sq: circle.r_sq,
+    ///rotate an oriented circle according to the turn. a möbius turn can send the inside of the circle to the outside.
+    ///returns None if the circle goes through the pole of a möbius turn
+    pub fn rot_oriented_circle(&self, circle: OrientedCircle) -> Option<OrientedCircle> {
+        match self.mobius() {
+            Some(mobius) => mobius.oriented_circle(circle),
+            None => Some(OrientedCircle {
+                circ: self.rot_circle(circle.circ)?,
+                ori: circle.ori,
+            }),
         }
     }
-    ///rotate an arc according to the turn. does not care whether the arc is in/out of the circle
-    pub fn rot_arc(&self, arc: Arc) -> Arc {
-        Arc {
-            circle: self.rot_circle(arc.circle),
-            start: self.rot_point(arc.start),
-            angle: arc.angle,
+    ///rotate an arc according to the turn. does not care whether the arc is in/out of the circle.
+    ///returns None if the arc's circle goes through the pole of a möbius turn
+    pub fn rot_arc(&self, arc: Arc) -> Option<Arc> {
+        match self.mobius() {
+            Some(mobius) => mobius.arc(arc),
+            None => Some(Arc {
+                circle: self.rot_circle(arc.circle)?,
+                start: self.rot_point(arc.start),
+                angle: arc.angle,
+            }),
         }
     }
-    ///rotate a shape according to the turn. does not care whether the shape is in/out of the circle
-    pub fn rot_pieceshape(&self, shape: &PieceShape) -> PieceShape {
-        PieceShape {
+    ///rotate a shape according to the turn. does not care whether the shape is in/out of the circle.
+    ///returns None if one of its circles goes through the pole of a möbius turn
+    pub fn rot_pieceshape(&self, shape: &PieceShape) -> Option<PieceShape> {
+        Some(PieceShape {
             bounds: shape
                 .bounds
                 .iter()
-                .map(|x| OrientedCircle {
-                    circ: self.rot_circle(x.circ),
-                    ori: x.ori,
-                })
-                .collect(),
-            border: shape.border.iter().map(|x| self.rot_arc(*x)).collect(),
-        }
+                .map(|x| self.rot_oriented_circle(*x))
+                .collect::<Option<_>>()?,
+            border: shape
+                .border
+                .iter()
+                .map(|x| self.rot_arc(*x))
+                .collect::<Option<_>>()?,
+        })
     }
-    ///turn a pieceshape according to the turn, without cutting. returns None if the turn is blocked by the piece.
-    ///pieces outside turn.circle are unaffected
+    ///turn a pieceshape according to the turn, without cutting. returns None if the turn is blocked by the piece, or
+    ///can't move it (see rot_pieceshape). pieces outside turn.circle are unaffected
     pub fn turn_pieceshape(&self, shape: &PieceShape) -> Option<PieceShape> {
         match shape.in_circle(self.circle) {
             None => None,
-            Some(Contains::Inside) | Some(Contains::Border) => Some(self.rot_pieceshape(shape)),
+            Some(Contains::Inside) | Some(Contains::Border) => self.rot_pieceshape(shape),
             Some(Contains::Outside) => Some(shape.clone()),
         }
     }
     ///turn a pieceshape according to the turn, with cutting. returns 1 or 2 pieces.
     ///if two shapes are returned, a cut was made and exactly one of the two shape was rotated.
     pub fn turn_cut_pieceshape(&self, shape: &PieceShape) -> Result<Vec<PieceShape>, String> {
+        let rotated = |shape: &PieceShape| {
+            self.rot_pieceshape(shape).ok_or(
+                "Turn.turn_cut_pieceshape failed: möbius turn sent a circle of the shape to a line!",
+            )
+        };
         match shape.in_circle(self.circle) {
             None => {
                 let (i, o) = shape
                     .cut_by_circle(self.circle)
                     .ok_or("Turn.turn_cut_pieceshape failed: shape crossed cut but was not cut!")?;
-                Ok(vec![self.rot_pieceshape(&i), o])
+                Ok(vec![rotated(&i)?, o])
             }
-            Some(Contains::Inside) | Some(Contains::Border) => Ok(vec![self.rot_pieceshape(shape)]),
+            Some(Contains::Inside) | Some(Contains::Border) => Ok(vec![rotated(shape)?]),
             Some(Contains::Outside) => Ok(vec![shape.clone()]),
         }
     }
@@ -130,6 +212,13 @@ impl OrderedTurn {
             },
         }
     }
+    ///move the turn by an isometry, see Turn::transformed
+    pub fn transformed(&self, isometry: &Isometry) -> Self {
+        Self {
+            turn: self.turn.transformed(isometry),
+            order: self.order,
+        }
+    }
     pub fn turn_piece(&self, piece: &Piece) -> Option<Piece> {
         self.turn.turn_piece(piece)
     }
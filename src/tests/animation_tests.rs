@@ -0,0 +1,58 @@
+use std::f64::consts::PI;
+
+use crate::{
+    complex::{c64::C64, complex_circle::ComplexCircle, point::Point, rotation::Rotation},
+    hps::custom_values::hpspuzzledata::HPSPuzzleData,
+    puzzle::{
+        animation::{AnimationOptions, encode_apng},
+        color::ColorScheme,
+        puzzle::Puzzle,
+        turn::{OrderedTurn, Turn},
+    },
+};
+
+#[test]
+fn test_animate() {
+    let disk = ComplexCircle {
+        center: Point(C64 { re: 0.0, im: 0.0 }),
+        r_sq: 1.0,
+    };
+    let mut data = HPSPuzzleData::new();
+    data.add_disk(disk);
+    data.turns.insert(
+        String::from("R"),
+        OrderedTurn {
+            turn: Turn {
+                circle: disk,
+                rot: Rotation::from_angle(-PI / 2.0),
+                pivot: None,
+            },
+            order: 4,
+        },
+    );
+    let puzzle = Puzzle::new(data.to_puzzle_data(&"test.hps".into()));
+    assert_eq!(
+        puzzle.parse_moves("R R2 R' R3'"),
+        Ok(vec![
+            (String::from("R"), 1),
+            (String::from("R"), 2),
+            (String::from("R"), -1),
+            (String::from("R"), -3),
+        ])
+    );
+    assert!(puzzle.parse_moves("L").is_err());
+    let options = AnimationOptions {
+        frames_per_turn: 4,
+        ..Default::default()
+    };
+    let frames = puzzle
+        .animate(
+            &puzzle.parse_moves("R R'").unwrap(),
+            &options,
+            &ColorScheme::default(),
+        )
+        .unwrap();
+    //the starting frame, then four frames for each turn
+    assert_eq!(frames.len(), 9);
+    assert!(encode_apng(&frames, &options).is_ok());
+}
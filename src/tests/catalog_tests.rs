@@ -0,0 +1,201 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use crate::{
+    DEF_PATH, DEFAULT_PUZZLE,
+    hps::data_storer::{
+        catalog::{Catalog, CatalogSort, PuzzleCounts, RECENT_COUNT, fuzzy_score},
+        data_storer::{DataStorer, PuzzleLoadingData},
+    },
+    puzzle::puzzle::PuzzleMetadata,
+    ui::settings::Settings,
+};
+
+///four puzzles made from the definition of the default puzzle, and their counts: Alpha and Beta were counted, Gamma
+///wasn't yet, and Delta failed to build
+fn catalog_puzzles() -> (
+    Vec<PuzzleLoadingData>,
+    HashMap<String, Option<PuzzleCounts>>,
+) {
+    let mut ds = DataStorer::new(false).unwrap();
+    ds.load_puzzles(DEF_PATH).unwrap();
+    let base = ds
+        .puzzles
+        .lock()
+        .unwrap()
+        .get(&PathBuf::from(DEFAULT_PUZZLE))
+        .unwrap();
+    let def = |name: &str, authors: &[&str], tags: &[&str], difficulty, created: &str| {
+        PuzzleLoadingData {
+            name: name.to_string(),
+            authors: authors.iter().map(|x| x.to_string()).collect(),
+            metadata: PuzzleMetadata {
+                tags: tags.iter().map(|x| x.to_string()).collect(),
+                difficulty,
+                created: created.to_string(),
+                ..PuzzleMetadata::default()
+            },
+            ..base.clone()
+        }
+    };
+    let defs = vec![
+        def("Alpha", &["Ann"], &["star"], Some(2), "2024-01-01"),
+        def("Beta", &["Bob"], &[], Some(1), "2025-06-01"),
+        def("Gamma", &["Ann", "Bob"], &["star"], None, ""),
+        def("Delta", &["Cy"], &[], Some(3), "2023-03-03"),
+    ];
+    let counts = |turns, pieces| {
+        Some(PuzzleCounts {
+            turns,
+            circles: 2,
+            pieces,
+        })
+    };
+    let counts = HashMap::from([
+        (defs[0].key(), counts(2, 10)),
+        (defs[1].key(), counts(4, 30)),
+        (defs[3].key(), None),
+    ]);
+    (defs, counts)
+}
+
+///the names of the puzzles the catalog finds, in order
+fn result_names(
+    catalog: &Catalog,
+    defs: &[PuzzleLoadingData],
+    counts: &HashMap<String, Option<PuzzleCounts>>,
+) -> Vec<String> {
+    catalog
+        .results(defs.to_vec(), counts)
+        .into_iter()
+        .map(|x| x.name)
+        .collect()
+}
+
+#[test]
+fn test_fuzzy_score() {
+    //every character has to appear in order, ignoring case and spaces
+    assert!(fuzzy_score("pent", "Pentagons").is_some());
+    assert!(fuzzy_score("3 circ", "Three Circles").is_none());
+    assert!(fuzzy_score("tc", "Three Circles").is_some());
+    assert!(fuzzy_score("ct", "Three Circles").is_none());
+    assert!(fuzzy_score("xyz", "Three Circles").is_none());
+    assert_eq!(fuzzy_score("", "anything"), Some(0));
+    //consecutive characters and word starts score higher than scattered ones
+    assert!(fuzzy_score("star", "Stars").unwrap() > fuzzy_score("star", "Oyster Pearl").unwrap());
+    assert!(fuzzy_score("ts", "Two Stars").unwrap() > fuzzy_score("ts", "Tuesdays").unwrap());
+}
+
+#[test]
+fn test_catalog_recent_and_favorites() {
+    let mut catalog = Catalog::default();
+    for i in 0..RECENT_COUNT + 2 {
+        catalog.played(&format!("puzzle {i}"));
+    }
+    catalog.played("puzzle 5");
+    //playing a puzzle again moves it to the front, and only the latest few are kept
+    assert_eq!(catalog.recent.len(), RECENT_COUNT);
+    assert_eq!(catalog.recent[0], "puzzle 5");
+    assert_eq!(catalog.recent[1], format!("puzzle {}", RECENT_COUNT + 1));
+    assert_eq!(
+        catalog.recent.iter().filter(|x| *x == "puzzle 5").count(),
+        1
+    );
+    catalog.toggle_favorite("a");
+    catalog.toggle_favorite("b");
+    catalog.toggle_favorite("a");
+    assert_eq!(catalog.favorites, vec![String::from("b")]);
+    //clearing the search keeps the favorites and recent puzzles
+    catalog.search = String::from("star");
+    catalog.turns = Some((1, 4));
+    assert!(catalog.is_filtering() && catalog.needs_counts());
+    catalog.clear();
+    assert!(!catalog.is_filtering());
+    assert_eq!(catalog.favorites.len(), 1);
+    assert_eq!(catalog.recent.len(), RECENT_COUNT);
+}
+
+#[test]
+fn test_favorites_and_recent_saved() {
+    let mut settings = Settings::default();
+    settings.favorites = vec![String::from("2 Circles/Stars"), String::from("Misc/Trio")];
+    settings.recent = vec![String::from("Misc/Trio")];
+    let loaded = Settings::from_string(&settings.to_string());
+    assert_eq!(loaded.favorites, settings.favorites);
+    assert_eq!(loaded.recent, settings.recent);
+}
+
+#[test]
+fn test_catalog_score() {
+    let (defs, counts) = catalog_puzzles();
+    //which of the puzzles the catalog keeps
+    let kept = |catalog: &Catalog| {
+        defs.iter()
+            .filter(|x| catalog.score(x, counts.get(&x.key())).is_some())
+            .map(|x| x.name.as_str())
+            .collect::<Vec<_>>()
+    };
+    let mut catalog = Catalog::default();
+    assert_eq!(kept(&catalog), vec!["Alpha", "Beta", "Gamma", "Delta"]);
+    catalog.author = Some(String::from("Ann"));
+    assert_eq!(kept(&catalog), vec!["Alpha", "Gamma"]);
+    catalog.author = None;
+    catalog.tag = Some(String::from("star"));
+    assert_eq!(kept(&catalog), vec!["Alpha", "Gamma"]);
+    catalog.tag = None;
+    //puzzles that haven't been counted yet are kept, and ones that failed to build are left out
+    catalog.turns = Some((3, 5));
+    assert_eq!(kept(&catalog), vec!["Beta", "Gamma"]);
+    catalog.turns = None;
+    catalog.pieces = Some((5, 20));
+    assert_eq!(kept(&catalog), vec!["Alpha", "Gamma"]);
+    catalog.pieces = None;
+    //the search matches names and authors
+    catalog.search = String::from("ga");
+    assert_eq!(kept(&catalog), vec!["Gamma"]);
+    catalog.search = String::from("bob");
+    assert_eq!(kept(&catalog), vec!["Beta", "Gamma"]);
+}
+
+#[test]
+fn test_catalog_sorts() {
+    let (defs, counts) = catalog_puzzles();
+    let mut catalog = Catalog::default();
+    let sorted = |catalog: &mut Catalog, sort| {
+        catalog.sort = sort;
+        result_names(catalog, &defs, &counts)
+    };
+    //puzzles missing the value sorted by go last, by name
+    assert_eq!(
+        sorted(&mut catalog, CatalogSort::Name),
+        vec!["Alpha", "Beta", "Delta", "Gamma"]
+    );
+    assert_eq!(
+        sorted(&mut catalog, CatalogSort::Newest),
+        vec!["Beta", "Alpha", "Delta", "Gamma"]
+    );
+    assert_eq!(
+        sorted(&mut catalog, CatalogSort::Difficulty),
+        vec!["Beta", "Alpha", "Delta", "Gamma"]
+    );
+    assert_eq!(
+        sorted(&mut catalog, CatalogSort::Pieces),
+        vec!["Alpha", "Beta", "Delta", "Gamma"]
+    );
+    assert_eq!(
+        sorted(&mut catalog, CatalogSort::Turns),
+        vec!["Alpha", "Beta", "Delta", "Gamma"]
+    );
+    //the best matches come first, by name or author
+    catalog.search = String::from("a");
+    assert_eq!(
+        sorted(&mut catalog, CatalogSort::Relevance),
+        vec!["Alpha", "Gamma", "Beta", "Delta"]
+    );
+    //filters apply to the sorted results too
+    catalog.search.clear();
+    catalog.turns = Some((3, 5));
+    assert_eq!(
+        sorted(&mut catalog, CatalogSort::Turns),
+        vec!["Beta", "Gamma"]
+    );
+}
@@ -55,3 +55,92 @@ fn test_circle() {
     };
     assert!(piece1.cut_by_circle(c1).is_none())
 }
+
+#[test]
+fn test_constructions() {
+    use crate::{
+        PRECISION,
+        complex::{complex_circle::ComplexCircle, point::Point},
+    };
+    use approx_collections::ApproxEq;
+
+    let p = |re, im| Point(C64 { re, im });
+    //the circle through three points of the unit circle is the unit circle
+    let unit = ComplexCircle::through(p(1.0, 0.0), p(0.0, 1.0), p(-1.0, 0.0)).unwrap();
+    assert!(unit.center.approx_eq(&p(0.0, 0.0), PRECISION));
+    assert!(unit.r_sq.approx_eq(&1.0, PRECISION));
+    assert!(ComplexCircle::through(p(0.0, 0.0), p(1.0, 1.0), p(2.0, 2.0)).is_none());
+    //reflecting across the line y = x swaps the coordinates
+    let reflected = p(2.0, 1.0)
+        .reflect_across(p(0.0, 0.0), p(1.0, 1.0))
+        .unwrap();
+    assert!(reflected.approx_eq(&p(1.0, 2.0), PRECISION));
+    assert!(
+        p(0.0, 0.0)
+            .midpoint(p(2.0, 4.0))
+            .approx_eq(&p(1.0, 2.0), PRECISION)
+    );
+    //a circle centered at (3, 0) touching the unit circle from outside has radius 2
+    let tangent = unit.tangent_at(p(3.0, 0.0)).unwrap();
+    assert!(tangent.r_sq.approx_eq(&4.0, PRECISION));
+    //circles of radius 1 touching two unit circles at (-1, 0) and (1, 0) are centered at (0, +-sqrt(3))
+    let left = ComplexCircle {
+        center: p(-1.0, 0.0),
+        r_sq: 1.0,
+    };
+    let right = ComplexCircle {
+        center: p(1.0, 0.0),
+        r_sq: 1.0,
+    };
+    let tangents = left.tangent_circles(right, 1.0);
+    assert_eq!(tangents.len(), 2);
+    assert!(
+        tangents
+            .iter()
+            .all(|x| x.center.0.im.abs().approx_eq(&3.0_f64.sqrt(), PRECISION))
+    );
+    //a radius that isn't positive gives no circles, rather than shrunk or empty ones
+    assert!(left.tangent_circles(right, 0.0).is_empty());
+    assert!(left.tangent_circles(right, -1.0).is_empty());
+    assert!(left.tangent_circles(right, f64::NAN).is_empty());
+}
+
+#[test]
+fn test_disk_around_disk() {
+    use crate::{
+        complex::{
+            complex_circle::{ComplexCircle, Contains},
+            point::Point,
+        },
+        hps::custom_values::hpspuzzledata::HPSPuzzleData,
+    };
+
+    let point = |re, im| Point(C64 { re, im });
+    let circle = |r_sq| ComplexCircle {
+        center: point(0.0, 0.0),
+        r_sq,
+    };
+    //a disk added around an earlier one has a hole where the earlier one is
+    let mut data = HPSPuzzleData::new();
+    data.add_disk(circle(0.25));
+    assert!(data.add_disk(circle(4.0)));
+    let ring = &data.pieces[1];
+    assert_eq!(ring.shape.border.len(), 2);
+    assert_eq!(ring.in_circle(circle(0.25)), Some(Contains::Outside));
+    assert_eq!(ring.in_circle(circle(1.0)), None);
+    //and it isn't drawn over the hole
+    let render = ring.clone().triangulate(25.0);
+    let covers = |p: Point| {
+        render
+            .triangulations
+            .iter()
+            .flat_map(|x| &x.inside)
+            .any(|[a, b, c]| {
+                let side = |x: Point, y: Point| ((y - x).0 * (p - x).0.conj()).im;
+                let (d1, d2, d3) = (side(*a, *b), side(*b, *c), side(*c, *a));
+                (d1 >= 0.0 && d2 >= 0.0 && d3 >= 0.0) || (d1 <= 0.0 && d2 <= 0.0 && d3 <= 0.0)
+            })
+    };
+    assert!(covers(point(1.0, 0.3)) && covers(point(-0.2, -1.5)));
+    assert!(!covers(point(0.1, 0.1)));
+}
@@ -0,0 +1,53 @@
+use crate::{
+    puzzle::color::{Color, Marking},
+    ui::settings::Settings,
+};
+
+#[test]
+fn test_hex_colors() {
+    assert_eq!(Color::from_hex("#ff8000"), Some(Color::rgb(255, 128, 0)));
+    assert_eq!(Color::from_hex("f80"), Some(Color::rgb(255, 136, 0)));
+    assert_eq!(
+        Color::from_hex("#00000080"),
+        Some(Color::Rgba([0, 0, 0, 128]))
+    );
+    assert_eq!(Color::from_hex("#ff800"), None);
+    assert_eq!(Color::from_hex("#+f+f+f"), None);
+    assert_eq!(Color::rgb(1, 2, 254).to_hex().as_deref(), Some("#0102fe"));
+}
+
+#[test]
+fn test_color_schemes_round_trip() {
+    let mut settings = Settings::default();
+    settings
+        .color_scheme
+        .colors
+        .insert(Color::rgb(255, 0, 0), Color::rgb(200, 0, 0));
+    settings
+        .color_scheme
+        .markings
+        .insert(Color::rgb(255, 0, 0), Marking::Glyph(String::from("R")));
+    settings
+        .color_scheme
+        .markings
+        .insert(Color::rgb(0, 0, 255), Marking::Hatching);
+    settings
+        .puzzle_color_schemes
+        .entry(String::from("2 Circles/Pentagons/Stars"))
+        .or_default()
+        .colors
+        .insert(Color::None, Color::rgb(0, 0, 0));
+    let loaded = Settings::from_string(&settings.to_string());
+    assert_eq!(loaded.color_scheme, settings.color_scheme);
+    assert_eq!(loaded.puzzle_color_schemes, settings.puzzle_color_schemes);
+    //the puzzle's scheme is layered over the global one
+    let scheme = loaded
+        .color_scheme
+        .layered(&loaded.puzzle_color_schemes["2 Circles/Pentagons/Stars"]);
+    assert_eq!(scheme.apply(Color::None), Color::rgb(0, 0, 0));
+    assert_eq!(scheme.apply(Color::rgb(255, 0, 0)), Color::rgb(200, 0, 0));
+    assert_eq!(
+        scheme.marking(Color::rgb(0, 0, 255)),
+        Some(&Marking::Hatching)
+    );
+}
@@ -0,0 +1,109 @@
+use std::f64::consts::PI;
+
+use approx_collections::ApproxEq;
+
+use crate::{
+    PRECISION,
+    complex::{c64::C64, complex_circle::ComplexCircle, point::Point, rotation::Rotation},
+    hps::custom_values::hpspuzzledata::HPSPuzzleData,
+    puzzle::{
+        puzzle::Puzzle,
+        turn::{OrderedTurn, Turn},
+    },
+};
+
+///the angle of R, a fifth of a turn
+const ANGLE: f64 = 2.0 * PI / 5.0;
+
+///a disk with a turn R of the whole disk, and a turn B of a smaller circle that crosses the uncut disk
+fn puzzle() -> Puzzle {
+    let disk = ComplexCircle {
+        center: Point(C64 { re: 0.0, im: 0.0 }),
+        r_sq: 1.0,
+    };
+    let mut data = HPSPuzzleData::new();
+    data.add_disk(disk);
+    data.turns.insert(
+        String::from("R"),
+        OrderedTurn {
+            turn: Turn {
+                circle: disk,
+                rot: Rotation::from_angle(ANGLE),
+                pivot: None,
+            },
+            order: 5,
+        },
+    );
+    data.turns.insert(
+        String::from("B"),
+        OrderedTurn {
+            turn: Turn {
+                circle: ComplexCircle {
+                    center: Point(C64 { re: 0.5, im: 0.0 }),
+                    r_sq: 0.36,
+                },
+                rot: Rotation::from_angle(PI),
+                pivot: None,
+            },
+            order: 2,
+        },
+    );
+    Puzzle::new(data.to_puzzle_data(&"test.hps".into()))
+}
+
+///the angle the pieces are still rotated by, for the animation
+fn offset(puzzle: &Puzzle) -> f64 {
+    puzzle.animation_offset.unwrap().rot.angle()
+}
+
+#[test]
+fn test_preview_drag() {
+    let mut p = puzzle();
+    p.preview_drag("R", 0.3);
+    assert!(offset(&p).approx_eq(&0.3, PRECISION));
+    assert_eq!(p.anim_left, 1.0);
+    assert!(p.stack.is_empty());
+}
+
+#[test]
+fn test_finish_drag_rounding() {
+    //just under half a turn snaps back to nothing, and the whole swept angle animates back
+    let mut p = puzzle();
+    assert_eq!(p.finish_drag("R", 0.49 * ANGLE, false), Ok(false));
+    assert!(p.stack.is_empty());
+    assert!(offset(&p).approx_eq(&(0.49 * ANGLE), PRECISION));
+    //just over half a turn snaps to the turn, and the rest of it is animated
+    let mut p = puzzle();
+    assert_eq!(p.finish_drag("R", 0.51 * ANGLE, false), Ok(true));
+    assert_eq!(p.stack, vec![(String::from("R"), 1)]);
+    assert!(offset(&p).approx_eq(&(-0.49 * ANGLE), PRECISION));
+    //the same the other way around
+    let mut p = puzzle();
+    assert_eq!(p.finish_drag("R", -0.51 * ANGLE, false), Ok(true));
+    assert_eq!(p.stack, vec![(String::from("R"), -1)]);
+    assert!(offset(&p).approx_eq(&(0.49 * ANGLE), PRECISION));
+}
+
+#[test]
+fn test_finish_drag_normalizes() {
+    //four fifths of a turn is done as one fifth the other way
+    let mut p = puzzle();
+    assert_eq!(p.finish_drag("R", 4.0 * ANGLE + 0.1, false), Ok(true));
+    assert_eq!(p.stack, vec![(String::from("R"), -1)]);
+    assert!(offset(&p).approx_eq(&0.1, PRECISION));
+    //a full revolution does nothing
+    let mut p = puzzle();
+    assert_eq!(p.finish_drag("R", 2.0 * PI, false), Ok(false));
+    assert!(p.stack.is_empty());
+    assert!(offset(&p).approx_eq(&0.0, PRECISION));
+}
+
+#[test]
+fn test_finish_drag_bandaged() {
+    //B crosses the uncut disk, so it can't turn and animates back from where it was let go
+    let mut p = puzzle();
+    assert_eq!(p.finish_drag("B", 0.9 * PI, false), Ok(false));
+    assert!(p.stack.is_empty());
+    assert!(offset(&p).approx_eq(&(0.9 * PI), PRECISION));
+    assert!(p.finish_drag("L", 1.0, false).is_err());
+}
@@ -0,0 +1,213 @@
+use std::f64::consts::PI;
+
+use approx_collections::ApproxEq;
+
+use crate::{
+    PRECISION,
+    complex::{
+        c64::C64,
+        complex_circle::{ComplexCircle, Contains, OrientedCircle},
+        point::Point,
+        rotation::Rotation,
+    },
+    hps::custom_values::hpspuzzledata::HPSPuzzleData,
+    puzzle::{
+        color::Color,
+        filter::{FilterCache, FilterStyle, PieceFilter},
+        puzzle::Puzzle,
+        region::Region,
+        turn::{OrderedTurn, Turn},
+    },
+    ui::settings::Settings,
+};
+
+const RED: Color = Color::rgb(255, 0, 0);
+const BLUE: Color = Color::rgb(0, 0, 255);
+
+///a circle of radius 0.9 centered at (0, im), which cuts a lens off of the top or bottom of the unit disk
+fn lens_circle(im: f64) -> ComplexCircle {
+    ComplexCircle {
+        center: Point(C64 { re: 0.0, im }),
+        r_sq: 0.81,
+    }
+}
+
+///the unit disk with a red lens cut off of its top and a blue one off of its bottom, and a half turn T of the whole
+///disk, which swaps the lenses
+fn puzzle() -> Puzzle {
+    let disk = ComplexCircle {
+        center: Point(C64 { re: 0.0, im: 0.0 }),
+        r_sq: 1.0,
+    };
+    let mut data = HPSPuzzleData::new();
+    data.add_disk(disk);
+    for (im, color) in [(1.0, RED), (-1.0, BLUE)] {
+        let cut = OrderedTurn {
+            turn: Turn {
+                circle: lens_circle(im),
+                rot: Rotation::from_angle(PI / 2.0),
+                pivot: None,
+            },
+            order: 4,
+        };
+        data.cut(&vec![cut]).unwrap();
+        data.color(&Region::from(lens(im)), color);
+    }
+    data.turns.insert(
+        String::from("T"),
+        OrderedTurn {
+            turn: Turn {
+                circle: disk,
+                rot: Rotation::from_angle(PI),
+                pivot: None,
+            },
+            order: 2,
+        },
+    );
+    Puzzle::new(data.to_puzzle_data(&"test.hps".into()))
+}
+
+///the inside of lens_circle
+fn lens(im: f64) -> OrientedCircle {
+    OrientedCircle {
+        circ: lens_circle(im),
+        ori: Contains::Inside,
+    }
+}
+
+///the index of the first solved piece of a color
+fn piece(puzzle: &Puzzle, color: Color) -> usize {
+    puzzle
+        .data
+        .pieces
+        .iter()
+        .position(|x| x.color == color)
+        .unwrap()
+}
+
+///the indices of the pieces a filter selects
+fn selected(cache: &mut FilterCache, puzzle: &Puzzle, filter: &PieceFilter) -> Vec<usize> {
+    cache
+        .select(puzzle, filter)
+        .into_iter()
+        .enumerate()
+        .filter(|(_, x)| *x)
+        .map(|(i, _)| i)
+        .collect()
+}
+
+#[test]
+fn test_orbits() {
+    let puzzle = puzzle();
+    let (red, blue, middle) = (
+        piece(&puzzle, RED),
+        piece(&puzzle, BLUE),
+        piece(&puzzle, Color::None),
+    );
+    let orbits = puzzle.orbits();
+    assert_eq!(orbits.len(), 3);
+    //the lenses are swapped by T, and the middle is only moved onto itself
+    assert_eq!(orbits[red], orbits[blue]);
+    assert_ne!(orbits[red], orbits[middle]);
+    //the orbits are numbered in the order they first appear
+    assert_eq!(orbits[0], 0);
+    assert!(orbits.iter().all(|x| *x < 2));
+    let mut cache = FilterCache::default();
+    assert_eq!(cache.orbits(&puzzle), orbits.as_slice());
+}
+
+#[test]
+fn test_home_pieces() {
+    let mut puzzle = puzzle();
+    let (red, blue) = (piece(&puzzle, RED), piece(&puzzle, BLUE));
+    assert_eq!(puzzle.turn_id("T", false, 1), Ok(true));
+    //the red lens is now at the bottom, but belongs at the top
+    let solved = &puzzle.data.pieces;
+    assert!(
+        !puzzle.pieces[red]
+            .piece
+            .shape
+            .approx_eq(&solved[red].shape, PRECISION)
+    );
+    assert!(
+        puzzle.pieces[red]
+            .piece
+            .shape
+            .approx_eq(&solved[blue].shape, PRECISION)
+    );
+    let homes = puzzle.home_pieces();
+    for (i, home) in homes.iter().enumerate() {
+        assert!(home.shape.approx_eq(&solved[i].shape, PRECISION));
+        assert_eq!(puzzle.solved_index(home), Some(i));
+    }
+    let mut cache = FilterCache::default();
+    assert_eq!(cache.home(&puzzle, red), Some(red));
+}
+
+#[test]
+fn test_filter_select() {
+    let mut puzzle = puzzle();
+    let (red, blue, middle) = (
+        piece(&puzzle, RED),
+        piece(&puzzle, BLUE),
+        piece(&puzzle, Color::None),
+    );
+    let mut cache = FilterCache::default();
+    assert_eq!(
+        selected(&mut cache, &puzzle, &PieceFilter::new("All")),
+        vec![0, 1, 2]
+    );
+    let mut colors = PieceFilter::new("Colors");
+    colors.colors = vec![RED, Color::None];
+    let mut expected = vec![red, middle];
+    expected.sort();
+    assert_eq!(selected(&mut cache, &puzzle, &colors), expected);
+    let mut top = PieceFilter::new("Top");
+    top.region = Region::from(lens(1.0));
+    assert_eq!(selected(&mut cache, &puzzle, &top), vec![red]);
+    let mut lenses = PieceFilter::new("Lenses");
+    lenses.orbits = vec![puzzle.orbits()[red]];
+    let mut expected = vec![red, blue];
+    expected.sort();
+    assert_eq!(selected(&mut cache, &puzzle, &lenses), expected);
+    //the region and orbits are where the pieces belong, not where they are now, and the cache notices the turn
+    assert_eq!(puzzle.turn_id("T", false, 1), Ok(true));
+    assert_eq!(selected(&mut cache, &puzzle, &top), vec![red]);
+    assert_eq!(selected(&mut cache, &puzzle, &lenses), expected);
+    //every criterion has to match
+    top.colors = vec![BLUE];
+    assert!(selected(&mut cache, &puzzle, &top).is_empty());
+}
+
+#[test]
+fn test_piece_filters_round_trip() {
+    let mut filter = PieceFilter::new("Edges");
+    filter.colors = vec![Color::rgb(255, 0, 0), Color::None];
+    let circle = OrientedCircle {
+        circ: ComplexCircle {
+            center: Point(C64 { re: 0.5, im: -1.0 }),
+            r_sq: 2.0,
+        },
+        ori: Contains::Outside,
+    };
+    filter.region = Region::from(circle);
+    filter.orbits = vec![1, 3];
+    filter.style = FilterStyle::Hidden;
+    filter.key = Some(egui::Key::E);
+    let mut settings = Settings::default();
+    settings
+        .piece_filters
+        .insert(String::from("2 Circles/Pentagons/Stars"), vec![filter]);
+    let loaded = Settings::from_string(&settings.to_string());
+    let loaded = &loaded.piece_filters["2 Circles/Pentagons/Stars"][0];
+    assert_eq!(loaded.name, "Edges");
+    assert_eq!(loaded.colors, vec![Color::rgb(255, 0, 0), Color::None]);
+    assert_eq!(loaded.region.0.len(), 1);
+    assert_eq!(loaded.region.0[0].len(), 1);
+    assert_eq!(loaded.region.0[0][0].circ.center.0.re, 0.5);
+    assert_eq!(loaded.region.0[0][0].circ.r_sq, 2.0);
+    assert_eq!(loaded.region.0[0][0].ori, Contains::Outside);
+    assert_eq!(loaded.orbits, vec![1, 3]);
+    assert_eq!(loaded.style, FilterStyle::Hidden);
+    assert_eq!(loaded.key, Some(egui::Key::E));
+}
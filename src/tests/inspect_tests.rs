@@ -0,0 +1,55 @@
+use std::f64::consts::PI;
+
+use crate::{
+    complex::{c64::C64, complex_circle::ComplexCircle, point::Point, rotation::Rotation},
+    hps::custom_values::hpspuzzledata::HPSPuzzleData,
+    puzzle::{
+        filter::FilterCache,
+        puzzle::Puzzle,
+        turn::{OrderedTurn, Turn},
+    },
+};
+
+#[test]
+fn test_inspect_piece() {
+    let disk = ComplexCircle {
+        center: Point(C64 { re: 0.0, im: 0.0 }),
+        r_sq: 1.0,
+    };
+    let mut data = HPSPuzzleData::new();
+    data.add_disk(disk);
+    //R turns the whole disk, and L crosses it, so the disk blocks L
+    for (id, center) in [("R", 0.0), ("L", 1.0)] {
+        data.turns.insert(
+            String::from(id),
+            OrderedTurn {
+                turn: Turn {
+                    circle: ComplexCircle {
+                        center: Point(C64 {
+                            re: center,
+                            im: 0.0,
+                        }),
+                        r_sq: 1.0,
+                    },
+                    rot: Rotation::from_angle(-PI / 2.0),
+                    pivot: None,
+                },
+                order: 4,
+            },
+        );
+    }
+    let mut puzzle = Puzzle::new(data.to_puzzle_data(&"test.hps".into()));
+    assert_eq!(puzzle.piece_at(Point(C64 { re: 0.5, im: 0.0 })), Some(0));
+    assert_eq!(puzzle.piece_at(Point(C64 { re: 2.0, im: 0.0 })), None);
+    let mut cache = FilterCache::default();
+    let info = puzzle.piece_info(0, cache.home(&puzzle, 0)).unwrap();
+    assert_eq!(info.home, Some(0));
+    assert_eq!(info.moved_by, vec![String::from("R")]);
+    assert_eq!(info.blocked, vec![String::from("L")]);
+    assert_eq!(info.components, 1);
+    assert!(info.in_place);
+    assert!(puzzle.piece_info(1, None).is_none());
+    //the piece still belongs in the same place after it is turned
+    puzzle.turn_id("R", false, 1).unwrap();
+    assert_eq!(cache.home(&puzzle, 0), Some(0));
+}
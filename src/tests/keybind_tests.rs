@@ -0,0 +1,119 @@
+use crate::hps::data_storer::keybind_data::KeybindData;
+
+#[test]
+fn test_keybind_diagnostics() {
+    let config = String::from(
+        "binds {
+    d L -1
+    notakey L 1
+    f L
+    g L x
+    d R 1
+}
+override Stars {
+    j R 2
+}
+binds {
+    d L 3
+}",
+    );
+    let (data, diagnostics) = KeybindData::load_from_string(config).unwrap();
+    //the valid binds are kept, with the later duplicate winning, even from another block
+    assert_eq!(
+        data.defaults.get(&egui::Key::D),
+        Some(&(String::from("L"), 3))
+    );
+    assert_eq!(data.defaults.len(), 1);
+    assert_eq!(data.overrides.len(), 1);
+    //one problem is reported for each bad line, in order
+    let lines = diagnostics.iter().map(|x| x.line).collect::<Vec<usize>>();
+    assert_eq!(lines, vec![3, 4, 5, 6, 14]);
+    assert_eq!(diagnostics[0].col, 5);
+}
+
+#[test]
+fn test_override_globs() {
+    use crate::hps::data_storer::keybind_data::glob_match;
+
+    assert!(glob_match(
+        "2 Circles/*/stars*",
+        "2 Circles/Pentagons/stars_2.hps"
+    ));
+    assert!(!glob_match("2 Circles/*", "2 Circles/Pentagons/stars.hps"));
+    assert!(glob_match("2 Circles/**", "2 Circles/Pentagons/stars.hps"));
+    assert!(glob_match("**/stars.hps", "2 Circles/Pentagons/stars.hps"));
+    assert!(!glob_match(
+        "**/stars.hps",
+        "2 Circles/Pentagons/split_stars.hps"
+    ));
+    assert!(glob_match("?? Circles/**", "10 Circles/big.hps"));
+}
+
+#[test]
+fn test_override_precedence() {
+    use std::path::PathBuf;
+
+    use crate::{DEF_PATH, DEFAULT_PUZZLE, hps::data_storer::data_storer::DataStorer};
+
+    let mut ds = DataStorer::new(false).unwrap();
+    ds.load_puzzles(DEF_PATH).unwrap();
+    let mut puzzle = ds
+        .puzzles
+        .lock()
+        .unwrap()
+        .get(&PathBuf::from(DEFAULT_PUZZLE))
+        .unwrap();
+    puzzle.metadata.tags = vec![String::from("star")];
+    //the overrides are written from highest to lowest precedence, so file order can't be what decides
+    let config = format!(
+        "binds {{
+    a X 1
+    b X 1
+    c X 1
+    d X 1
+    e X 1
+    f X 1
+}}
+override {:?} {{
+    a N 1
+}}
+override glob=\"2 Circles/**\" {{
+    a G 1
+    b G 1
+}}
+override folder=\"2 Circles/Pentagons\" {{
+    a F2 1
+    b F2 1
+    c F2 1
+}}
+override folder=\"2 Circles\" {{
+    a F1 1
+    b F1 1
+    c F1 1
+    d F1 1
+}}
+override tag=\"star\" {{
+    a T 1
+    b T 1
+    c T 1
+    d T 1
+    e T 1
+}}
+override folder=\"3 Circles\" {{
+    f Z 1
+}}",
+        puzzle.name
+    );
+    let (data, diagnostics) = KeybindData::load_from_string(config).unwrap();
+    assert!(diagnostics.is_empty());
+    let binds = data.get_keybinds_for_puzzle(&puzzle);
+    let turn = |key: egui::Key| binds[&key].0.as_str();
+    //names beat globs, which beat folders (deeper first), which beat tags, which beat the defaults
+    assert_eq!(turn(egui::Key::A), "N");
+    assert_eq!(turn(egui::Key::B), "G");
+    assert_eq!(turn(egui::Key::C), "F2");
+    assert_eq!(turn(egui::Key::D), "F1");
+    assert_eq!(turn(egui::Key::E), "T");
+    //overrides for other puzzles don't apply
+    assert_eq!(turn(egui::Key::F), "X");
+}
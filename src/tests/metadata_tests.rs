@@ -0,0 +1,75 @@
+use std::path::Path;
+
+use crate::{
+    hps::{builtins::is_date, data_storer::data_storer::DataStorer},
+    puzzle::puzzle::MAX_DIFFICULTY,
+};
+
+#[test]
+fn test_is_date() {
+    assert!(is_date("2024-01-31"));
+    assert!(is_date("1999-12-01"));
+    //february has a 29th only in leap years
+    assert!(is_date("2024-02-29"));
+    assert!(is_date("2000-02-29"));
+    assert!(!is_date("2023-02-29"));
+    assert!(!is_date("1900-02-29"));
+    //days that don't exist in their month
+    assert!(!is_date("2024-02-31"));
+    assert!(!is_date("2024-04-31"));
+    assert!(!is_date("2024-01-00"));
+    assert!(!is_date("2024-01-32"));
+    //months that don't exist
+    assert!(!is_date("2024-13-01"));
+    assert!(!is_date("2024-00-10"));
+    //wrong lengths and separators
+    assert!(!is_date("24-01-01"));
+    assert!(!is_date("2024-1-01"));
+    assert!(!is_date("2024-01-1"));
+    assert!(!is_date("2024-01"));
+    assert!(!is_date("2024-01-01-01"));
+    assert!(!is_date("2024/01/01"));
+    assert!(!is_date(""));
+    //non-digits, including signs that parse would accept
+    assert!(!is_date("20x4-01-01"));
+    assert!(!is_date("2024-+1-01"));
+}
+
+#[test]
+fn test_metadata_checks() {
+    let mut ds = DataStorer::new(false).unwrap();
+    let puzzles = [
+        ("Easy", 1, "2024-02-29"),
+        ("Hardest", MAX_DIFFICULTY, ""),
+        ("Too Hard", MAX_DIFFICULTY + 1, ""),
+        ("Bad Date", 1, "2024-02-31"),
+    ];
+    //each puzzle is in its own file, since an error stops the rest of the file
+    for (i, (name, difficulty, created)) in puzzles.iter().enumerate() {
+        let source = format!(
+            "add_puzzle(
+    name = {name:?},
+    authors = [\"Test\"],
+    difficulty = {difficulty},
+    created = {created:?},
+    build = fn() {{
+        add_circles([circle(0.0, 0.0, 1.0)])
+    }},
+)"
+        );
+        ds.rt
+            .modules
+            .add_file(Path::new(&format!("Test/puzzle_{i}.hps")), source.as_str());
+    }
+    ds.rt.exec_all_files();
+    let mut names = ds
+        .puzzles
+        .lock()
+        .unwrap()
+        .defs()
+        .into_iter()
+        .map(|x| x.name)
+        .collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(names, vec![String::from("Easy"), String::from("Hardest")]);
+}
@@ -0,0 +1,103 @@
+use std::f64::consts::PI;
+
+use approx_collections::ApproxEq;
+
+use crate::{
+    PRECISION,
+    complex::{
+        arc::Arc,
+        c64::C64,
+        complex_circle::{ComplexCircle, Orientation},
+        mobius::Mobius,
+        point::Point,
+        rotation::Rotation,
+    },
+    hps::custom_values::hpspuzzledata::HPSPuzzleData,
+    puzzle::turn::{OrderedTurn, Turn},
+};
+
+#[test]
+fn test_mobius() {
+    let point = |re, im| Point(C64 { re, im });
+    let disk = ComplexCircle {
+        center: point(0.0, 0.0),
+        r_sq: 1.0,
+    };
+    let pivot = point(0.3, 0.2);
+    let mobius = Mobius::elliptic(disk, pivot, Rotation::from_angle(2.0 * PI / 3.0)).unwrap();
+    //the pivot is fixed, the circle is sent to itself, and three turns do nothing
+    assert!(mobius.point(pivot).approx_eq(&pivot, PRECISION));
+    assert!(mobius.circle(disk).unwrap().approx_eq(&disk, PRECISION));
+    let start = point(-0.5, 0.1);
+    let thrice = mobius.point(mobius.point(mobius.point(start)));
+    assert!(thrice.approx_eq(&start, PRECISION));
+    assert!(Mobius::elliptic(disk, point(2.0, 0.0), Rotation::from_angle(PI)).is_none());
+    //arcs are sent to the arcs through the images of their points, both ways around
+    for ori in [Orientation::CCW, Orientation::CW] {
+        let arc = Arc::from_endpoints(
+            ComplexCircle {
+                center: point(0.2, -0.3),
+                r_sq: 0.16,
+            },
+            point(0.6, -0.3),
+            point(0.2, 0.1),
+            ori,
+        );
+        let image = mobius.arc(arc).unwrap();
+        assert!(image.start.approx_eq(&mobius.point(arc.start), PRECISION));
+        assert!(image.end().approx_eq(&mobius.point(arc.end()), PRECISION));
+        assert!(
+            image
+                .midpoint()
+                .approx_eq(&mobius.point(arc.midpoint()), PRECISION)
+        );
+    }
+    //cutting, turning the cut around the pivot and cutting again cuts the moved pieces too. the cut circle is inside
+    //the disk and crosses its image, so the disk with a hole and the disk cut out of it are each cut in two
+    let mut data = HPSPuzzleData::new();
+    data.add_disk(disk);
+    let turn = OrderedTurn {
+        turn: Turn {
+            circle: disk,
+            rot: Rotation::from_angle(2.0 * PI / 3.0),
+            pivot: Some(pivot),
+        },
+        order: 3,
+    };
+    let cut = OrderedTurn {
+        turn: Turn {
+            circle: ComplexCircle {
+                center: point(0.5, 0.0),
+                r_sq: 0.09,
+            },
+            rot: Rotation::from_angle(PI),
+            pivot: None,
+        },
+        order: 2,
+    };
+    data.cut(&vec![cut, turn, cut]).unwrap();
+    assert_eq!(data.pieces.len(), 4);
+    //the borders of the pieces are still closed: every arc ends where another one starts
+    for piece in &data.pieces {
+        for arc in &piece.shape.border {
+            assert!(
+                piece
+                    .shape
+                    .border
+                    .iter()
+                    .any(|x| x.start.approx_eq(&arc.end(), PRECISION))
+            );
+        }
+    }
+    //a circle through the pole would be sent to a line, so turning a piece bordered by one fails
+    let pole = mobius.pole().unwrap();
+    let through = ComplexCircle {
+        center: point(0.5, 0.7),
+        r_sq: point(0.5, 0.7).dist_sq(pole),
+    };
+    assert!(turn.turn.rot_circle(through).is_none());
+    assert!(turn.turn.rot_circle(disk).is_some());
+    let mut data = HPSPuzzleData::new();
+    data.add_disk(through);
+    assert!(data.turn(turn, true).is_err());
+}
@@ -1 +1,21 @@
+pub mod animation_tests;
+pub mod catalog_tests;
 pub mod circle_tests;
+pub mod color_tests;
+pub mod drag_tests;
+pub mod filter_tests;
+pub mod inspect_tests;
+pub mod keybind_tests;
+pub mod metadata_tests;
+pub mod mobius_tests;
+pub mod puzzle_io_tests;
+pub mod raster_tests;
+pub mod region_tests;
+pub mod render_tests;
+pub mod settings_tests;
+pub mod sphere_tests;
+pub mod stats_tests;
+pub mod svg_tests;
+pub mod symmetry_tests;
+pub mod touch_tests;
+pub mod watcher_tests;
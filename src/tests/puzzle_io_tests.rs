@@ -0,0 +1,38 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use crate::{
+    DEF_PATH, DEFAULT_PUZZLE,
+    hps::data_storer::{data_storer::DataStorer, puzzle_io::PuzzleIOData},
+    puzzle::puzzle::Puzzle,
+};
+
+#[test]
+fn test_load_old_log() {
+    let mut ds = DataStorer::new(false).unwrap();
+    ds.load_puzzles(DEF_PATH).unwrap();
+    let def = ds
+        .puzzles
+        .lock()
+        .unwrap()
+        .get(&PathBuf::from(DEFAULT_PUZZLE))
+        .unwrap();
+    let turn = def
+        .load(&mut ds.rt, HashMap::new())
+        .unwrap()
+        .turns
+        .keys()
+        .next()
+        .unwrap()
+        .clone();
+    //a log as it has always been written: the path of the definition file, which isn't named after the puzzle
+    assert_ne!(def.path.file_name().unwrap(), def.name.as_str());
+    let log = format!(
+        "name {:?}\npath {:?}\nsolve {{\n\tturn {:?} 1\n}}",
+        def.name,
+        def.path.to_string_lossy(),
+        turn
+    );
+    let puzzle = Puzzle::from_io_data(PuzzleIOData::from_string(log).unwrap(), &mut ds).unwrap();
+    assert_eq!(puzzle.name, def.name);
+    assert_eq!(puzzle.stack, vec![(turn, 1)]);
+}
@@ -0,0 +1,34 @@
+use crate::{
+    complex::{c64::C64, complex_circle::ComplexCircle, point::Point},
+    hps::custom_values::hpspuzzledata::HPSPuzzleData,
+    puzzle::{
+        color::ColorScheme,
+        puzzle::Puzzle,
+        raster::{Image, RasterOptions},
+    },
+};
+
+#[test]
+fn test_rasterize() {
+    let mut data = HPSPuzzleData::new();
+    data.add_disk(ComplexCircle {
+        center: Point(C64 { re: 0.0, im: 0.0 }),
+        r_sq: 1.0,
+    });
+    let puzzle = Puzzle::new(data.to_puzzle_data(&"test.hps".into()));
+    let image = puzzle.rasterize(
+        &RasterOptions {
+            size: 20,
+            outline_width: 0.0,
+            solved: false,
+        },
+        &ColorScheme::default(),
+    );
+    assert_eq!((image.width, image.height), (20, 20));
+    //the middle is filled with the color of uncolored pieces, and the corners are outside the disk
+    assert_eq!(image.get(10, 10), [160, 160, 160, 255]);
+    assert_eq!(image.get(0, 0), [0, 0, 0, 0]);
+    assert_eq!(image.get(19, 19), [0, 0, 0, 0]);
+    //writing and reading a png gives back the same image
+    assert_eq!(Image::from_png(&image.to_png().unwrap()).unwrap(), image);
+}
@@ -0,0 +1,90 @@
+use crate::{
+    complex::{
+        c64::C64,
+        complex_circle::{ComplexCircle, Contains, OrientedCircle},
+        point::Point,
+    },
+    puzzle::{filter::PieceFilter, region::Region},
+    ui::settings::Settings,
+};
+
+#[test]
+fn test_region_operations() {
+    let circle = |re| OrientedCircle {
+        circ: ComplexCircle {
+            center: Point(C64 { re, im: 0.0 }),
+            r_sq: 1.0,
+        },
+        ori: Contains::Inside,
+    };
+    let p = |re| Point(C64 { re, im: 0.0 });
+    let (left, right) = (Region::from(circle(-0.5)), Region::from(circle(0.5)));
+    let both = left.intersection(&right);
+    let either = left.union(&right);
+    let only_left = left.difference(&right);
+    assert!(both.contains(p(0.0)) && !both.contains(p(-1.0)));
+    assert!(either.contains(p(-1.0)) && either.contains(p(1.0)) && !either.contains(p(2.0)));
+    assert!(only_left.contains(p(-1.0)) && !only_left.contains(p(0.0)));
+    //the complement of the union is outside of both circles
+    let outside = either.complement();
+    assert!(outside.contains(p(2.0)) && !outside.contains(p(0.0)));
+    //a region and its complement have nothing in common
+    assert!(left.intersection(&left.complement()).0.is_empty());
+    assert!(Region::nowhere().complement().is_everywhere());
+}
+
+#[test]
+fn test_region_contains_piece() {
+    use crate::hps::custom_values::hpspuzzledata::HPSPuzzleData;
+
+    let circle = |re| OrientedCircle {
+        circ: ComplexCircle {
+            center: Point(C64 { re, im: 0.0 }),
+            r_sq: 1.0,
+        },
+        ori: Contains::Inside,
+    };
+    //a small disk on the seam between two circles is in their union, but in neither part on its own, so it isn't
+    //counted as in the region
+    let mut data = HPSPuzzleData::new();
+    data.add_disk(ComplexCircle {
+        center: Point(C64 { re: 0.0, im: 0.0 }),
+        r_sq: 0.04,
+    });
+    let piece = &data.pieces[0];
+    let (left, right) = (Region::from(circle(-0.9)), Region::from(circle(0.9)));
+    assert!(!left.contains_piece(piece) && !right.contains_piece(piece));
+    assert!(!left.union(&right).contains_piece(piece));
+    //it is counted once a single part covers it
+    assert!(left.union(&Region::from(circle(0.0))).contains_piece(piece));
+}
+
+#[test]
+fn test_region_round_trip() {
+    //regions are saved with the piece filters that use them, and a region of several parts is kept
+    let circle = OrientedCircle {
+        circ: ComplexCircle {
+            center: Point(C64 { re: 0.5, im: -1.0 }),
+            r_sq: 2.0,
+        },
+        ori: Contains::Outside,
+    };
+    let mut union = PieceFilter::new("Union");
+    union.region = Region::from(circle).union(&Region::from(-circle));
+    //the empty region has no parts, so it has to be kept apart from the whole plane, which has one empty part
+    let mut empty = PieceFilter::new("Empty");
+    empty.region = Region::nowhere();
+    let mut settings = Settings::default();
+    settings.piece_filters.insert(
+        String::from("2 Circles/Pentagons/Stars"),
+        vec![union, empty, PieceFilter::new("All")],
+    );
+    let loaded = Settings::from_string(&settings.to_string());
+    let filters = &loaded.piece_filters["2 Circles/Pentagons/Stars"];
+    let region = &filters[0].region;
+    assert_eq!(region.0.len(), 2);
+    assert_eq!(region.0[0][0].ori, Contains::Outside);
+    assert_eq!(region.0[1][0].ori, Contains::Inside);
+    assert!(filters[1].region.0.is_empty());
+    assert!(filters[2].region.is_everywhere());
+}
@@ -0,0 +1,140 @@
+use egui::{Pos2, Rect, pos2, vec2};
+
+use crate::{
+    complex::{c64::C64, point::Point},
+    ui::{
+        render::{View, screen_triangles},
+        settings::Settings,
+    },
+};
+
+///whether two points are equal up to the precision of screen coordinates
+fn close(a: Point, b: Point) -> bool {
+    (a.0.re - b.0.re).abs() < 1e-3 && (a.0.im - b.0.im).abs() < 1e-3
+}
+
+#[test]
+fn test_screen_round_trip() {
+    //a rect that doesn't start at the origin of the screen, like the puzzle's panel next to the side panel
+    let rect = Rect::from_min_size(pos2(250.0, 40.0), vec2(800.0, 600.0));
+    let points = [
+        Point(C64 { re: 0.0, im: 0.0 }),
+        Point(C64 { re: 0.7, im: -0.2 }),
+        Point(C64 { re: -1.5, im: 2.25 }),
+    ];
+    for mirrored in [false, true] {
+        let view = View {
+            scale_factor: 1.5,
+            offset: vec2(0.3, -0.1),
+            rotation: 0.7,
+            mirrored,
+            orthographic: false,
+        };
+        for p in points {
+            let pos = p.to_pos2(&rect, &view);
+            assert!(close(Point::from_pos2(&pos, &rect, &view).unwrap(), p));
+        }
+    }
+    //with no pan, the origin is drawn in the middle of the rect, not of the screen
+    let view = View {
+        scale_factor: 1.0,
+        offset: vec2(0.0, 0.0),
+        rotation: 0.0,
+        mirrored: false,
+        orthographic: false,
+    };
+    let center = points[0].to_pos2(&rect, &view);
+    assert!((center - rect.center()).length() < 1e-3);
+    //mirroring flips the right of the puzzle to the left of the screen
+    let right = Point(C64 { re: 1.0, im: 0.0 });
+    assert!(right.to_pos2(&rect, &view).x > rect.center().x);
+    let view = View {
+        mirrored: true,
+        ..view
+    };
+    assert!(right.to_pos2(&rect, &view).x < rect.center().x);
+}
+
+#[test]
+fn test_orthographic_round_trip() {
+    let rect = Rect::from_min_size(pos2(250.0, 40.0), vec2(800.0, 600.0));
+    let view = View {
+        scale_factor: 300.0,
+        offset: vec2(0.1, 0.2),
+        rotation: -0.4,
+        mirrored: true,
+        orthographic: true,
+    };
+    let north = Point(C64 { re: 0.3, im: -0.2 });
+    let south = Point(C64 { re: 1.5, im: 2.0 });
+    let equator = Point(C64 { re: 0.6, im: 0.8 });
+    //points are drawn on their own hemisphere, and points on the equator can be drawn on either
+    for (p, on_south) in [
+        (north, false),
+        (south, true),
+        (equator, false),
+        (equator, true),
+    ] {
+        let pos = p.to_pos2_on(&rect, &view, on_south);
+        assert!(close(Point::from_pos2(&pos, &rect, &view).unwrap(), p));
+    }
+    assert!(close(
+        Point::from_pos2(&south.to_pos2(&rect, &view), &rect, &view).unwrap(),
+        south
+    ));
+    //the south pole is drawn across the middle of the screen from the north pole, and can't be clicked
+    let view = View {
+        scale_factor: 500.0,
+        offset: vec2(0.0, 0.0),
+        rotation: 0.0,
+        mirrored: false,
+        orthographic: true,
+    };
+    let rect = Rect::from_min_size(pos2(0.0, 0.0), vec2(1920.0, 1080.0));
+    let origin = Point(C64 { re: 0.0, im: 0.0 }).to_pos2(&rect, &view);
+    let pole = pos2(2.0 * rect.center().x - origin.x, origin.y);
+    assert!(Point::from_pos2(&pole, &rect, &view).is_none());
+    assert!(Point::from_pos2(&origin, &rect, &view).is_some());
+}
+
+#[test]
+fn test_orthographic_triangles() {
+    let rect = Rect::from_min_size(pos2(0.0, 0.0), vec2(1920.0, 1080.0));
+    let view = View {
+        scale_factor: 400.0,
+        offset: vec2(0.0, 0.0),
+        rotation: 0.0,
+        mirrored: false,
+        orthographic: true,
+    };
+    //the centers of the hemispheres on the screen, and their radius
+    let north = pos2(rect.center().x - 1.1 * 400.0, rect.center().y);
+    let south = pos2(rect.center().x + 1.1 * 400.0, rect.center().y);
+    //a triangle crossing the equator, with a vertex far into the southern hemisphere
+    let triangle = [
+        Point(C64 { re: 0.2, im: 0.0 }),
+        Point(C64 { re: 8.0, im: -1.0 }),
+        Point(C64 { re: 0.5, im: 3.0 }),
+    ];
+    let triangles = screen_triangles(triangle, &rect, &view);
+    //every triangle is drawn whole on one of the hemispheres, and the triangle is drawn on both
+    let on = |center: Pos2, t: &[Pos2; 3]| t.iter().all(|x| (*x - center).length() < 400.5);
+    assert!(triangles.iter().all(|t| on(north, t) || on(south, t)));
+    assert!(triangles.iter().any(|t| on(north, t)));
+    assert!(triangles.iter().any(|t| on(south, t)));
+    //the triangles are small enough to follow the curve of the sphere
+    assert!(triangles.iter().all(|[a, b, c]| {
+        (*a - *b)
+            .length()
+            .max((*b - *c).length())
+            .max((*c - *a).length())
+            < 40.0
+    }));
+}
+
+#[test]
+fn test_mirrored_view_saved() {
+    let mut settings = Settings::default();
+    settings.view.mirrored = true;
+    assert!(Settings::from_string(&settings.to_string()).view.mirrored);
+}
@@ -0,0 +1,51 @@
+use crate::ui::settings::Settings;
+
+#[test]
+fn test_settings_round_trip() {
+    let mut settings = Settings::default();
+    settings.animation_speed = 12.5;
+    settings.view.offset = egui::vec2(0.25, -1.0);
+    settings.cut_on_turn = true;
+    settings.log_path = String::from("my \"log\"");
+    let loaded = Settings::from_string(&settings.to_string());
+    assert_eq!(loaded.animation_speed, 12.5);
+    assert_eq!(loaded.view.offset, egui::vec2(0.25, -1.0));
+    assert!(loaded.cut_on_turn);
+    assert_eq!(loaded.log_path, "my \"log\"");
+    assert!(loaded.puzzle.is_none());
+}
+
+#[test]
+fn test_settings_fall_back_to_defaults() {
+    //invalid and out of range values are replaced, but the valid ones are kept
+    let loaded =
+        Settings::from_string("animation_speed \"fast\"\noutline_width 100\ncut_on_turn #true");
+    assert_eq!(loaded.animation_speed, Settings::default().animation_speed);
+    assert_eq!(loaded.outline_width, 10.0);
+    assert!(loaded.cut_on_turn);
+    //a file that isn't kdl at all gives the defaults
+    assert_eq!(
+        Settings::from_string("{{{").log_path,
+        Settings::default().log_path
+    );
+}
+
+#[test]
+fn test_save_only_changed() {
+    let path = std::env::temp_dir().join(format!("circleguy_settings_{}.kdl", std::process::id()));
+    let mut settings = Settings::default();
+    let mut last_saved = String::new();
+    assert_eq!(settings.save_to(&path, &mut last_saved), Ok(true));
+    assert_eq!(last_saved, settings.to_string());
+    //unchanged settings aren't written again, even if the file changed since
+    std::fs::write(&path, "").unwrap();
+    assert_eq!(settings.save_to(&path, &mut last_saved), Ok(false));
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "");
+    settings.cut_on_turn = !settings.cut_on_turn;
+    assert_eq!(settings.save_to(&path, &mut last_saved), Ok(true));
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        settings.to_string()
+    );
+    std::fs::remove_file(&path).unwrap();
+}
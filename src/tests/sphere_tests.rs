@@ -0,0 +1,141 @@
+use std::f64::consts::PI;
+
+use approx_collections::ApproxEq;
+
+use crate::{
+    PRECISION,
+    complex::{
+        c64::C64,
+        complex_circle::Contains,
+        point::Point,
+        rotation::Rotation,
+        sphere::{SpherePoint, cap},
+    },
+    puzzle::turn::Turn,
+};
+
+#[test]
+fn test_sphere() {
+    let point = |re, im| Point(C64 { re, im });
+    let axis = |x, y, z| SpherePoint::from_direction(x, y, z).unwrap();
+    //projecting and unprojecting gets back the same point, and the south pole can't be projected
+    let p = SpherePoint::from_point(point(0.3, -2.0));
+    assert!(
+        p.to_point()
+            .unwrap()
+            .approx_eq(&point(0.3, -2.0), PRECISION)
+    );
+    assert!(axis(0.0, 0.0, -1.0).to_point().is_none());
+    //the northern hemisphere is the unit disk, and the southern one is outside of it
+    let north = cap(axis(0.0, 0.0, 1.0), PI / 2.0).unwrap();
+    assert!(north.circ.center.approx_eq(&point(0.0, 0.0), PRECISION));
+    assert!(north.circ.r_sq.approx_eq(&1.0, PRECISION));
+    assert_eq!(north.ori, Contains::Inside);
+    assert_eq!(
+        cap(axis(0.0, 0.0, -1.0), PI / 2.0).unwrap().ori,
+        Contains::Outside
+    );
+    //caps whose edges go through the south pole are lines
+    assert!(cap(axis(1.0, 0.0, 0.0), PI / 2.0).is_none());
+    //a quarter turn clockwise around the x axis takes (0.8, 0, 0.6) to (0.8, 0.6, 0)
+    let turn = Turn::spherical(
+        axis(1.0, 0.0, 0.0),
+        PI / 3.0,
+        Rotation::from_angle(-PI / 2.0),
+    )
+    .unwrap();
+    let start = axis(0.8, 0.0, 0.6).to_point().unwrap();
+    let end = axis(0.8, 0.6, 0.0).to_point().unwrap();
+    assert!(turn.rot_point(start).approx_eq(&end, PRECISION));
+    //a cap containing the south pole turns the rest of the sphere the other way instead, around the north pole
+    let turn = Turn::spherical(axis(0.0, 0.0, -1.0), 1.0, Rotation::from_angle(-PI / 2.0)).unwrap();
+    assert!(
+        turn.circle
+            .approx_eq(&cap(axis(0.0, 0.0, -1.0), 1.0).unwrap().circ, PRECISION)
+    );
+    assert!(turn.center().approx_eq(&point(0.0, 0.0), PRECISION));
+    let start = axis(0.8, 0.0, 0.6).to_point().unwrap();
+    let end = axis(0.0, -0.8, 0.6).to_point().unwrap();
+    assert!(turn.rot_point(start).approx_eq(&end, PRECISION));
+    //but its edge still can't go through the south pole
+    assert!(Turn::spherical(axis(1.0, 0.0, 0.0), PI / 2.0, Rotation::from_angle(PI)).is_none());
+}
+
+#[test]
+fn test_pole() {
+    let axis = |x, y, z| SpherePoint::from_direction(x, y, z).unwrap();
+    let close = |a: SpherePoint, b: SpherePoint| a.dist(&b) < 1e-9;
+    //projecting from a pole turns it to the south pole, keeping the distances between points
+    let (a, b) = (axis(0.3, -1.0, 0.2), axis(-2.0, 0.5, 1.0));
+    for pole in [
+        axis(1.0, 2.0, -3.0),
+        axis(0.0, 0.0, 1.0),
+        axis(-1.0, 0.0, 0.0),
+    ] {
+        assert!(close(pole.from_pole(pole), SpherePoint::SOUTH_POLE));
+        assert!(pole.antipode().from_pole(pole).z.approx_eq(&1.0, PRECISION));
+        assert!(
+            a.from_pole(pole)
+                .dist(&b.from_pole(pole))
+                .approx_eq(&a.dist(&b), PRECISION)
+        );
+    }
+    //the default pole doesn't move anything
+    assert!(close(a.from_pole(SpherePoint::SOUTH_POLE), a));
+}
+
+#[test]
+fn test_whole_sphere() {
+    use crate::{
+        complex::complex_circle::ComplexCircle, hps::custom_values::hpspuzzledata::HPSPuzzleData,
+    };
+
+    let point = |re, im| Point(C64 { re, im });
+    let circle = |re, r_sq| ComplexCircle {
+        center: point(re, 0.0),
+        r_sq,
+    };
+    //in a triangulation, whether the point is inside one of the triangles
+    let covers = |triangles: &[[Point; 3]], p: Point| {
+        triangles.iter().any(|[a, b, c]| {
+            let side = |x: Point, y: Point| ((y - x).0 * (p - x).0.conj()).im;
+            let (d1, d2, d3) = (side(*a, *b), side(*b, *c), side(*c, *a));
+            (d1 >= 0.0 && d2 >= 0.0 && d3 >= 0.0) || (d1 <= 0.0 && d2 <= 0.0 && d3 <= 0.0)
+        })
+    };
+    let mut data = HPSPuzzleData::new();
+    assert!(data.add_sphere());
+    //the sphere crosses every circle, and is drawn everywhere
+    let sphere = &data.pieces[0];
+    assert!(sphere.shape.contains_infinity());
+    assert_eq!(sphere.in_circle(circle(0.0, 1.0)), None);
+    let render = sphere.clone().triangulate(25.0);
+    assert!(covers(
+        &render.triangulations[0].inside,
+        point(100.0, -50.0)
+    ));
+    //a circle cuts it into a disk and the rest of the sphere, which is outside of the circle
+    data.add_disk(circle(0.0, 1.0));
+    assert_eq!(data.pieces.len(), 2);
+    let (disk, rest) = (&data.pieces[0], &data.pieces[1]);
+    assert!(!disk.shape.contains_infinity() && rest.shape.contains_infinity());
+    assert_eq!(rest.in_circle(circle(0.0, 1.0)), Some(Contains::Outside));
+    //the rest crosses a bigger circle around it, and a circle inside of it that doesn't meet its border
+    assert_eq!(rest.in_circle(circle(0.0, 4.0)), None);
+    assert_eq!(rest.in_circle(circle(3.0, 1.0)), None);
+    //which cuts a hole out of it
+    data.add_disk(circle(3.0, 1.0));
+    assert_eq!(data.pieces.len(), 3);
+    let rest = data
+        .pieces
+        .iter()
+        .find(|x| x.shape.contains_infinity())
+        .unwrap();
+    assert_eq!(rest.shape.border.len(), 2);
+    let render = rest.clone().triangulate(25.0);
+    let triangles = &render.triangulations[0].inside;
+    assert!(covers(triangles, point(1.5, 0.5)) && covers(triangles, point(-50.0, 20.0)));
+    assert!(!covers(triangles, point(0.0, 0.2)) && !covers(triangles, point(3.0, -0.2)));
+    //the sphere can only be added first
+    assert!(!data.add_sphere());
+}
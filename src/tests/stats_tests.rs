@@ -0,0 +1,42 @@
+use crate::hps::data_storer::{catalog::PuzzleCounts, stats::CatalogStats};
+
+#[test]
+fn test_catalog_stats() {
+    let counts = |turns, circles, pieces| {
+        Some(Some(PuzzleCounts {
+            turns,
+            circles,
+            pieces,
+        }))
+    };
+    let authors = |names: &[&str]| names.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+    let mut stats = CatalogStats::default();
+    stats.add("2 Circles/Stars", &authors(&["Ann"]), counts(2, 2, 30));
+    stats.add(
+        "2 Circles/Moons",
+        &authors(&["Ann", "Bo"]),
+        counts(2, 2, 12),
+    );
+    stats.add("6 Circles/Ring", &authors(&["Bo"]), counts(6, 6, 90));
+    stats.add("6 Circles/Broken", &authors(&["Cy"]), Some(None));
+    stats.add("7 Circles/Big", &authors(&["Bo"]), None);
+    assert_eq!(stats.puzzles, 5);
+    assert_eq!(stats.top_authors(2), vec![("Bo", 3), ("Ann", 2)]);
+    assert_eq!(
+        stats.folders(),
+        vec![("2 Circles", 2), ("6 Circles", 2), ("7 Circles", 1)]
+    );
+    //only the puzzles that were built are counted by their circles and pieces
+    assert_eq!(stats.circles(), vec![(2, 2), (6, 1)]);
+    assert_eq!(stats.pieces, 132);
+    assert_eq!((stats.failed, stats.uncounted), (1, 1));
+    let complex = stats
+        .most_complex(2)
+        .into_iter()
+        .map(|(key, _)| key)
+        .collect::<Vec<_>>();
+    assert_eq!(complex, vec!["6 Circles/Ring", "2 Circles/Stars"]);
+    let report = stats.to_string();
+    assert!(report.starts_with("5 puzzles by 3 authors"));
+    assert!(report.contains("\t6 Circles/Ring: 90 pieces, 6 turns"));
+}
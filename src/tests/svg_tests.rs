@@ -0,0 +1,28 @@
+use crate::{
+    complex::{c64::C64, complex_circle::ComplexCircle, point::Point},
+    hps::custom_values::hpspuzzledata::HPSPuzzleData,
+    puzzle::{color::ColorScheme, puzzle::Puzzle, svg::SvgOptions},
+};
+
+#[test]
+fn test_svg_export() {
+    let mut data = HPSPuzzleData::new();
+    data.add_disk(ComplexCircle {
+        center: Point(C64 { re: 0.0, im: 0.0 }),
+        r_sq: 1.0,
+    });
+    let puzzle = Puzzle::new(data.to_puzzle_data(&"test.hps".into()));
+    let svg = puzzle.to_svg(
+        &SvgOptions {
+            size: 100.0,
+            outline_width: 0.0,
+            solved: false,
+        },
+        &ColorScheme::default(),
+    );
+    //one piece, a full circle, which has to be written as two half circle arcs
+    assert_eq!(svg.matches("<path").count(), 1);
+    assert_eq!(svg.matches(" A ").count() + svg.matches("\"A ").count(), 2);
+    assert!(svg.contains("width=\"100.00\" height=\"100.00\""));
+    assert!(svg.contains("M 100.000 50.000 A 50.000 50.000 0 0 0 0.000 50.000"));
+}
@@ -0,0 +1,162 @@
+use std::f64::consts::PI;
+
+use approx_collections::ApproxEq;
+
+use crate::{
+    PRECISION,
+    complex::{
+        c64::C64,
+        complex_circle::{ComplexCircle, Contains, OrientedCircle},
+        point::Point,
+        rotation::Rotation,
+        symmetry::{Isometry, Symmetry},
+    },
+    hps::custom_values::hpspuzzledata::HPSPuzzleData,
+    puzzle::{
+        region::Region,
+        turn::{OrderedTurn, Turn},
+    },
+};
+
+#[test]
+fn test_symmetry() {
+    let origin = Point(C64 { re: 0.0, im: 0.0 });
+    let circle = |re, im, r_sq| ComplexCircle {
+        center: Point(C64 { re, im }),
+        r_sq,
+    };
+    let cyclic = Symmetry {
+        center: origin,
+        order: 4,
+        dihedral: false,
+        axis: 0.0,
+    };
+    let dihedral = Symmetry {
+        dihedral: true,
+        ..cyclic
+    };
+    assert_eq!(cyclic.elements().len(), 4);
+    assert_eq!(dihedral.elements().len(), 8);
+    //a circle on a line of reflection has as many images either way, but one off of it has twice as many
+    let on_axis = OrientedCircle {
+        circ: circle(1.0, 0.0, 0.25),
+        ori: Contains::Inside,
+    };
+    let off_axis = OrientedCircle {
+        circ: circle(1.0, 0.3, 0.25),
+        ori: Contains::Inside,
+    };
+    assert_eq!(cyclic.orbit_circle(on_axis).len(), 4);
+    assert_eq!(dihedral.orbit_circle(on_axis).len(), 4);
+    assert_eq!(dihedral.orbit_circle(off_axis).len(), 8);
+    //reflections reverse the direction of turns
+    let turn = Turn {
+        circle: circle(1.0, 0.0, 0.25),
+        rot: Rotation::from_angle(PI / 2.0),
+        pivot: None,
+    };
+    let reflected = turn.transformed(&dihedral.elements()[4]);
+    assert!((reflected.rot.angle() + PI / 2.0).abs() < 1e-9);
+    //cutting with a symmetry of order 2 cuts on both sides of the disk
+    let mut data = HPSPuzzleData::new();
+    data.add_disk(circle(0.0, 0.0, 1.0));
+    let cut = OrderedTurn {
+        turn: Turn {
+            circle: circle(1.0, 0.0, 0.25),
+            rot: Rotation::from_angle(PI / 2.0),
+            pivot: None,
+        },
+        order: 4,
+    };
+    let half = Symmetry { order: 2, ..cyclic };
+    data.symmetric_cut(&half, &Region::everywhere(), &[cut])
+        .unwrap();
+    assert_eq!(data.pieces.len(), 3);
+}
+
+#[test]
+fn test_mirror() {
+    let point = |re, im| Point(C64 { re, im });
+    let mut data = HPSPuzzleData::new();
+    data.add_disk(ComplexCircle {
+        center: point(0.0, 0.0),
+        r_sq: 1.0,
+    });
+    let cut = OrderedTurn {
+        turn: Turn {
+            circle: ComplexCircle {
+                center: point(1.0, 0.5),
+                r_sq: 0.5,
+            },
+            rot: Rotation::from_angle(PI / 2.0),
+            pivot: None,
+        },
+        order: 4,
+    };
+    data.cut(&vec![cut]).unwrap();
+    //mirror across the y axis
+    let mirror = Isometry::reflection_through(point(0.0, 0.0), point(0.0, 1.0)).unwrap();
+    data.transform(&mirror);
+    //the borders of the mirrored pieces still join up, and contain the mirrored points
+    for piece in &data.pieces {
+        let border = &piece.shape.border;
+        for (i, arc) in border.iter().enumerate() {
+            assert!(
+                arc.end()
+                    .approx_eq(&border[(i + 1) % border.len()].start, PRECISION)
+            );
+        }
+    }
+    //the piece cut off on the right is now on the left
+    let cut_off = data
+        .pieces
+        .iter()
+        .find(|x| x.shape.contains(point(-0.8, 0.3)) == Contains::Inside)
+        .unwrap();
+    assert_eq!(cut_off.shape.contains(point(-0.5, 0.5)), Contains::Inside);
+    assert_eq!(cut_off.shape.contains(point(0.8, 0.3)), Contains::Outside);
+}
+
+#[test]
+fn test_transformed_symmetry() {
+    let point = |re, im| Point(C64 { re, im });
+    let symmetry = Symmetry {
+        center: point(0.2, -0.1),
+        order: 3,
+        dihedral: true,
+        axis: 0.3,
+    };
+    let isometries = [
+        Isometry::Rotation {
+            center: point(1.0, 0.0),
+            angle: 0.7,
+        },
+        Isometry::Reflection {
+            center: point(0.5, 0.5),
+            angle: 1.1,
+        },
+    ];
+    let p = point(0.9, 0.4);
+    for isometry in isometries {
+        //moving the images of a point gives the images of the moved point under the moved symmetry
+        let moved = symmetry
+            .transformed(&isometry)
+            .orbit_point(isometry.point(p));
+        let expected = symmetry.orbit_point(p);
+        assert_eq!(moved.len(), expected.len());
+        for image in expected {
+            let image = isometry.point(image);
+            assert!(moved.iter().any(|x| x.approx_eq(&image, PRECISION)));
+        }
+    }
+    //the symmetric commands use the moved symmetry once the puzzle is moved
+    let mut data = HPSPuzzleData::new();
+    data.symmetry = Some(symmetry);
+    data.transform(&isometries[0]);
+    assert!(
+        data.symmetry
+            .unwrap()
+            .center
+            .approx_eq(&isometries[0].point(symmetry.center), PRECISION)
+    );
+}
@@ -0,0 +1,87 @@
+use egui::{Event, Pos2, RawInput, TouchDeviceId, TouchId, TouchPhase, pos2};
+
+use crate::ui::touch::{TouchAction, TouchState};
+
+///make a touch event for the first touch device
+fn touch(id: u64, phase: TouchPhase, pos: Pos2) -> Event {
+    Event::Touch {
+        device_id: TouchDeviceId(0),
+        id: TouchId(id),
+        phase,
+        pos,
+        force: None,
+    }
+}
+
+///run one frame of egui with the events, and feed the resulting touch info to the state
+fn run_frame(
+    ctx: &egui::Context,
+    state: &mut TouchState,
+    time: f64,
+    events: Vec<Event>,
+) -> Option<TouchAction> {
+    let mut action = None;
+    let _ = ctx.run(
+        RawInput {
+            time: Some(time),
+            events,
+            ..Default::default()
+        },
+        |ctx| action = state.update(ctx.input(|i| i.multi_touch())),
+    );
+    action
+}
+
+#[test]
+fn test_touch_gestures() {
+    let ctx = egui::Context::default();
+    let mut state = TouchState::default();
+    //egui starts gestures at the pointer position from the previous frame
+    let pointer = vec![Event::PointerMoved(pos2(150.0, 100.0))];
+    assert_eq!(run_frame(&ctx, &mut state, 0.0, pointer), None);
+    //pinching two fingers apart zooms in
+    let start = vec![
+        touch(0, TouchPhase::Start, pos2(100.0, 100.0)),
+        touch(1, TouchPhase::Start, pos2(200.0, 100.0)),
+    ];
+    assert_eq!(run_frame(&ctx, &mut state, 0.05, start), None);
+    let pinch = vec![
+        touch(0, TouchPhase::Move, pos2(50.0, 100.0)),
+        touch(1, TouchPhase::Move, pos2(250.0, 100.0)),
+    ];
+    match run_frame(&ctx, &mut state, 0.1, pinch) {
+        Some(TouchAction::Camera { zoom, .. }) => assert!((zoom - 2.0).abs() < 0.01),
+        x => panic!("expected a zoom, got {x:?}"),
+    }
+    let end = vec![
+        touch(0, TouchPhase::End, pos2(50.0, 100.0)),
+        touch(1, TouchPhase::End, pos2(250.0, 100.0)),
+    ];
+    assert_eq!(run_frame(&ctx, &mut state, 0.2, end), None);
+    //twisting two fingers starts a turn where the gesture started, and lifting them ends it
+    let start = vec![
+        touch(2, TouchPhase::Start, pos2(100.0, 100.0)),
+        touch(3, TouchPhase::Start, pos2(200.0, 100.0)),
+    ];
+    assert_eq!(run_frame(&ctx, &mut state, 0.3, start), None);
+    let (c, s) = (50.0 * 0.5f32.cos(), 50.0 * 0.5f32.sin());
+    let twist = vec![
+        touch(2, TouchPhase::Move, pos2(150.0 - c, 100.0 - s)),
+        touch(3, TouchPhase::Move, pos2(150.0 + c, 100.0 + s)),
+    ];
+    match run_frame(&ctx, &mut state, 0.4, twist) {
+        Some(TouchAction::StartTurn { pos, rotation }) => {
+            assert_eq!(pos, pos2(150.0, 100.0));
+            assert!((rotation - 0.5).abs() < 0.01);
+        }
+        x => panic!("expected a turn, got {x:?}"),
+    }
+    let end = vec![
+        touch(2, TouchPhase::End, pos2(150.0 - c, 100.0 - s)),
+        touch(3, TouchPhase::End, pos2(150.0 + c, 100.0 + s)),
+    ];
+    assert_eq!(
+        run_frame(&ctx, &mut state, 0.5, end),
+        Some(TouchAction::EndTurn)
+    );
+}
@@ -0,0 +1,196 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use crate::hps::data_storer::{
+    catalog::PuzzleCounts,
+    data_storer::DataStorer,
+    def_entry::DefEntry,
+    watcher::{FileChanges, FileWatcher},
+};
+
+#[test]
+fn test_file_watcher() {
+    let dir = std::env::temp_dir().join(format!("circleguy_watcher_{}", std::process::id()));
+    let defs = dir.join("Definitions");
+    std::fs::create_dir_all(defs.join("2 Circles")).unwrap();
+    let file = defs.join("2 Circles/stars.hps");
+    let keybinds = dir.join("keybinds.kdl");
+    std::fs::write(&file, "").unwrap();
+    std::fs::write(defs.join("notes.txt"), "").unwrap();
+    let mut watcher = FileWatcher::new(&defs.to_string_lossy(), &keybinds.to_string_lossy());
+    assert!(watcher.changes().is_empty());
+    //set the modification time by hand, since writing twice in a row can give the same time
+    let touch = |path: &PathBuf, secs| {
+        std::fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+            .unwrap();
+    };
+    touch(&file, 1000);
+    std::fs::write(defs.join("new.hps"), "").unwrap();
+    std::fs::write(&keybinds, "").unwrap();
+    let changes = watcher.changes();
+    assert_eq!(
+        changes.changed,
+        vec![
+            PathBuf::from("2 Circles/stars.hps"),
+            PathBuf::from("new.hps")
+        ]
+    );
+    assert!(changes.removed.is_empty());
+    assert!(changes.keybinds);
+    //files that aren't definitions are ignored, and deleted definitions are found
+    std::fs::write(defs.join("notes.txt"), "changed").unwrap();
+    std::fs::remove_file(&file).unwrap();
+    let changes = watcher.changes();
+    assert!(changes.changed.is_empty() && !changes.keybinds);
+    assert_eq!(changes.removed, vec![PathBuf::from("2 Circles/stars.hps")]);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+///a definition file adding a one circle puzzle for each name
+fn definitions(names: &[&str]) -> String {
+    names
+        .iter()
+        .map(|name| {
+            format!(
+                "add_puzzle(
+    name = {name:?},
+    authors = [\"Test\"],
+    build = fn() {{
+        add_circles([circle(0.0, 0.0, 1.0)])
+    }},
+)
+"
+            )
+        })
+        .collect()
+}
+
+///the names of the entries in the top folder of the definitions
+fn folders(ds: &DataStorer) -> Vec<String> {
+    let puzzles = ds.puzzles.lock().unwrap();
+    let DefEntry::Folder((_, entries)) = &*puzzles else {
+        panic!("the definitions should be a folder");
+    };
+    let mut folders = entries
+        .keys()
+        .map(|x| x.to_string_lossy().to_string())
+        .collect::<Vec<_>>();
+    folders.sort();
+    folders
+}
+
+#[test]
+fn test_remove_file() {
+    let mut ds = DataStorer::new(false).unwrap();
+    let files = [
+        ("Test/a.hps", definitions(&["Alpha", "Beta"])),
+        ("Test/b.hps", definitions(&["Gamma"])),
+        ("Other/Nested/c.hps", definitions(&["Delta"])),
+    ];
+    for (path, source) in &files {
+        ds.rt.modules.add_file(Path::new(path), source.as_str());
+    }
+    ds.rt.exec_all_files();
+    assert_eq!(ds.puzzles.lock().unwrap().defs().len(), 4);
+    //every puzzle of the file is removed, whatever the extension of the path
+    let mut removed = ds
+        .puzzles
+        .lock()
+        .unwrap()
+        .remove_file(Path::new("Test/a"))
+        .into_iter()
+        .map(|x| x.name)
+        .collect::<Vec<_>>();
+    removed.sort();
+    assert_eq!(removed, vec![String::from("Alpha"), String::from("Beta")]);
+    let puzzles = ds.puzzles.lock().unwrap().clone();
+    assert!(puzzles.get(Path::new("Test/Alpha")).is_none());
+    assert!(puzzles.get(Path::new("Test/Gamma")).is_some());
+    //the folder still has a puzzle, so it is kept
+    assert_eq!(folders(&ds), vec!["Other", "Test"]);
+    //removing the only puzzle of nested folders removes them all
+    let removed = ds
+        .puzzles
+        .lock()
+        .unwrap()
+        .remove_file(Path::new("Other/Nested/c.hps"));
+    assert_eq!(removed.len(), 1);
+    assert_eq!(folders(&ds), vec!["Test"]);
+    //a file with no puzzles removes nothing
+    assert!(
+        ds.puzzles
+            .lock()
+            .unwrap()
+            .remove_file(Path::new("Test/missing.hps"))
+            .is_empty()
+    );
+    assert_eq!(ds.puzzles.lock().unwrap().defs().len(), 1);
+}
+
+#[test]
+fn test_reload_changed() {
+    let dir = std::env::temp_dir().join(format!("circleguy_reload_{}", std::process::id()));
+    let defs = dir.join("Definitions");
+    std::fs::create_dir_all(defs.join("Test")).unwrap();
+    let mut ds = DataStorer::new(false).unwrap();
+    let files = [
+        ("Test/a.hps", definitions(&["Alpha", "Beta"])),
+        ("Other/b.hps", definitions(&["Gamma"])),
+        ("Kept/c.hps", definitions(&["Zeta"])),
+    ];
+    for (path, source) in &files {
+        ds.rt.modules.add_file(Path::new(path), source.as_str());
+    }
+    ds.rt.exec_all_files();
+    //pretend every puzzle was counted and has no thumbnail
+    let counts = PuzzleCounts {
+        turns: 0,
+        circles: 0,
+        pieces: 1,
+    };
+    for data in ds.puzzles.lock().unwrap().defs() {
+        ds.counts.insert(data.key(), Some(counts));
+        ds.thumbnails.0.insert(data.key(), None);
+    }
+    //Beta is renamed to Delta, and the file of Gamma is deleted
+    std::fs::write(defs.join("Test/a.hps"), definitions(&["Alpha", "Delta"])).unwrap();
+    let changes = FileChanges {
+        changed: vec![PathBuf::from("Test/a.hps")],
+        removed: vec![PathBuf::from("Other/b.hps")],
+        keybinds: false,
+    };
+    let keys = ds.reload_changed(
+        &defs.to_string_lossy(),
+        &dir.join("keybinds.kdl").to_string_lossy(),
+        &changes,
+    );
+    assert_eq!(
+        keys,
+        vec!["Other/Gamma", "Test/Alpha", "Test/Beta", "Test/Delta"]
+    );
+    let mut names = ds
+        .puzzles
+        .lock()
+        .unwrap()
+        .defs()
+        .into_iter()
+        .map(|x| x.name)
+        .collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(names, vec!["Alpha", "Delta", "Zeta"]);
+    assert_eq!(folders(&ds), vec!["Kept", "Test"]);
+    //the counts and thumbnails of the changed puzzles are found again, and the rest are kept
+    for key in &keys {
+        assert!(!ds.counts.contains_key(key));
+        assert!(!ds.thumbnails.0.contains_key(key));
+    }
+    assert_eq!(ds.counts.get("Kept/Zeta"), Some(&Some(counts)));
+    assert!(ds.thumbnails.0.contains_key("Kept/Zeta"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
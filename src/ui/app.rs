@@ -1,16 +1,32 @@
-use std::ffi::OsString;
+use std::collections::HashMap;
+use std::f64::consts::PI;
 use std::path::PathBuf;
 
+use crate::complex::complex_circle::{Circle, Contains, OrientedCircle};
+use crate::hps::data_storer::catalog::{COUNT_CHECK_INTERVAL, Catalog};
 use crate::hps::data_storer::data_storer::DataStorer;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::hps::data_storer::io::write_string_to_file;
+use crate::hps::data_storer::stats::{CatalogStats, TOP};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::hps::data_storer::watcher::{FileWatcher, WATCH_INTERVAL};
+use crate::puzzle::color::{Color, ColorScheme, Marking};
+use crate::puzzle::filter::{FilterCache, FilterStyle, PieceFilter};
 use crate::puzzle::puzzle::*;
-use crate::ui::render::draw_circle;
-use crate::{DEF_PATH, DEFAULT_PUZZLE};
+use crate::puzzle::region::Region;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::puzzle::svg::SvgOptions;
+use crate::ui::render::{View, draw_circle};
+use crate::ui::settings::{DEFAULT_VIEW, Settings};
+use crate::ui::touch::{TouchAction, TouchState};
+use crate::{DEF_PATH, DEFAULT_PUZZLE, PRECISION};
+use approx_collections::ApproxEq;
 use egui::*;
 
-///default scale factor
-const SCALE_FACTOR: f32 = 500.0;
-///default animation speed
-const ANIMATION_SPEED: f64 = 5.0;
+///how often the settings and the current puzzle are autosaved
+const AUTOSAVE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(10);
+///how far the view rotates when the rotation keys are pressed
+const ROTATION_STEP: f32 = std::f32::consts::PI / 12.0;
 ///credits string
 const CREDITS: &str = "Created by Henry Pickle,
 with major help from:
@@ -18,6 +34,15 @@ Luna Harran (sonicpineapple)
 Andrew Farkas (HactarCE)
 cryofractal";
 
+#[derive(Debug)]
+///a turn being done by dragging the mouse around a turn circle
+struct DragTurn {
+    id: String,      //the id of the turn being dragged
+    last_angle: f64, //the angle of the pointer around the turn's center on the last frame
+    swept: f64,      //the total angle swept so far
+    touch: bool,     //whether the turn is being done with two fingers rather than the pointer
+}
+
 #[derive(Debug)]
 ///used for running the app. contains all puzzle and view data at runtime
 pub struct App {
@@ -27,15 +52,34 @@ pub struct App {
     curr_msg: String,                //current message (usually for errors)
     animation_speed: f64,            //speed at which animations happen
     last_frame_time: web_time::Instant, //the absolute time at which the last frame happened
+    #[cfg(not(target_arch = "wasm32"))]
+    last_autosave: web_time::Instant, //the time at which the settings were last autosaved
+    last_saved: String,              //the settings as they were last saved
     outline_width: f32,              //the width of the outlines
-    scale_factor: f32,               //the scale factor (zoom)
-    offset: Vec2,                    //the offset of the puzzle from the center of the screen (pan)
+    view: View,                      //the zoom, pan and rotation of the puzzle on the screen
     cut_on_turn: bool,               //whether or not turns should cut the puzzle
     preview: bool,                   //whether the solved state is being previewed
+    drag: Option<DragTurn>,          //the turn currently being dragged, if any
+    touch: TouchState,               //the two finger gesture currently happening, if any
+    color_scheme: ColorScheme,       //the color scheme for every puzzle
+    show_markings: bool,             //whether markings are drawn over pieces to tell colors apart
+    puzzle_color_schemes: HashMap<String, ColorScheme>, //color schemes for single puzzles, by PuzzleData::key
+    edit_puzzle_scheme: bool, //whether the colors menu edits this puzzle's scheme or every puzzle's
+    piece_filters: HashMap<String, Vec<PieceFilter>>, //the filter presets of each puzzle, by PuzzleData::key
+    active_filter: Option<(String, usize)>, //the puzzle key and index of the filter preset that is on, if any
+    filter_cache: FilterCache, //where pieces belong and which orbits they are in, kept for filtering and inspecting
+    inspect: bool,             //whether clicking a piece inspects it instead of turning
+    inspected: Option<usize>,  //the index of the piece being inspected, if any
+    inspected_count: usize,    //how many pieces the puzzle had when the piece was inspected
+    catalog: Catalog, //the search and filters of the puzzle list, and the favorite and recent puzzles
+    #[cfg(not(target_arch = "wasm32"))]
+    watcher: FileWatcher, //watches the definitions and keybinds, to reload them when they are edited
 }
 impl App {
-    ///initialize a new app, using some default settings (from the constants)
-    pub fn new(_cc: &eframe::CreationContext<'_>) -> Self {
+    ///initialize a new app, using the saved settings (or the defaults if there are none)
+    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
+        let settings = Settings::load(cc.storage);
+        let mut curr_msg = String::new();
         let mut data_storer = DataStorer::new(false).ok(); //initialize a new data storer
         let p = if let Some(ref mut ds) = data_storer {
             let _ = ds.load_puzzles(
@@ -44,38 +88,79 @@ impl App {
                 //"Configs/Keybinds/groups.kdl",
             );
             let _ = ds.load_keybinds("Configs/keybinds.kdl");
-            let p_data = &ds
-                .puzzles
-                .lock()
-                .unwrap()
-                .get(&PathBuf::from(DEFAULT_PUZZLE));
-            if let Some(in_data) = p_data.clone() {
-                in_data
-                    .load(
-                        &mut ds.rt,
-                        ds.keybinds
-                            .get_keybinds_for_puzzle(&in_data.path.file_name().unwrap()),
-                    )
-                    .ok()
-            } else {
-                None
+            //reopen the last puzzle where it was left off, if it still loads. otherwise open the default puzzle
+            match settings
+                .puzzle
+                .clone()
+                .and_then(|x| Puzzle::from_io_data(x, ds))
+            {
+                Some(puzzle) => Some(puzzle),
+                None => {
+                    if settings.puzzle.is_some() {
+                        curr_msg = String::from("Could not reopen the last puzzle!");
+                    }
+                    let p_data = &ds
+                        .puzzles
+                        .lock()
+                        .unwrap()
+                        .get(&PathBuf::from(DEFAULT_PUZZLE));
+                    if let Some(in_data) = p_data.clone() {
+                        in_data
+                            .load(&mut ds.rt, ds.keybinds.get_keybinds_for_puzzle(&in_data))
+                            .ok()
+                            .map(Puzzle::new)
+                    } else {
+                        None
+                    }
+                }
             }
         } else {
             None
         };
+        let mut app = Self::with_settings(data_storer, p, settings);
+        app.last_saved = app.settings().to_string();
+        app.curr_msg = curr_msg;
+        app
+    }
+    ///make an app showing a puzzle, with the view and controls set from the settings
+    fn with_settings(
+        data_storer: Option<DataStorer>,
+        puzzle: Option<Puzzle>,
+        settings: Settings,
+    ) -> Self {
         Self {
-            //return the default app
             data_storer,
-            puzzle: p.map(Puzzle::new),
-            log_path: String::from("logfile"),
+            puzzle,
+            log_path: settings.log_path,
             curr_msg: String::new(),
-            animation_speed: ANIMATION_SPEED,
+            animation_speed: settings.animation_speed,
             last_frame_time: web_time::Instant::now(),
-            outline_width: 5.0,
-            scale_factor: SCALE_FACTOR,
-            offset: vec2(0.0, 0.0),
-            cut_on_turn: false,
+            #[cfg(not(target_arch = "wasm32"))]
+            last_autosave: web_time::Instant::now(),
+            last_saved: String::new(),
+            outline_width: settings.outline_width,
+            view: settings.view,
+            cut_on_turn: settings.cut_on_turn,
             preview: false,
+            drag: None,
+            touch: TouchState::default(),
+            color_scheme: settings.color_scheme,
+            show_markings: settings.show_markings,
+            puzzle_color_schemes: settings.puzzle_color_schemes,
+            edit_puzzle_scheme: true,
+            piece_filters: settings.piece_filters,
+            active_filter: None,
+            filter_cache: FilterCache::default(),
+            inspect: false,
+            inspected: None,
+            inspected_count: 0,
+            catalog: Catalog {
+                favorites: settings.favorites,
+                recent: settings.recent,
+                ..Catalog::default()
+            },
+            #[cfg(not(target_arch = "wasm32"))]
+            watcher: FileWatcher::new(DEF_PATH, "Configs/keybinds.kdl"),
             // keybinds: if let Some(kb) = &p_data.keybinds
             //     && let Some(gr) = &p_data.keybind_groups
             //     && let Some(keybinds) = load_keybinds(&kb, &gr)
@@ -86,35 +171,164 @@ impl App {
             // },
         }
     }
+    ///save the settings to the config file if they have changed since they were last saved
+    #[cfg(not(target_arch = "wasm32"))]
+    fn autosave(&mut self) {
+        if let Err(x) = self.settings().save(&mut self.last_saved) {
+            log::warn!("{x}");
+        }
+        self.last_autosave = web_time::Instant::now();
+    }
+    ///reload the definitions and keybinds that were edited since they were last checked. if the current puzzle changed,
+    ///it is rebuilt with its scramble and moves reapplied, or solved if they no longer apply
+    #[cfg(not(target_arch = "wasm32"))]
+    fn hot_reload(&mut self) {
+        let Some(changes) = self.watcher.poll() else {
+            return;
+        };
+        let Some(ds) = &mut self.data_storer else {
+            return;
+        };
+        let keys = ds.reload_changed(DEF_PATH, "Configs/keybinds.kdl", &changes);
+        let Some(p) = &self.puzzle else {
+            return;
+        };
+        let key = p.data.key();
+        if !changes.keybinds && !keys.contains(&key) {
+            return;
+        }
+        let name = p.name.clone();
+        if let Some(puzzle) = Puzzle::from_io_data(p.to_io_data(), ds) {
+            self.puzzle = Some(puzzle);
+            self.curr_msg = format!("Reloaded {name}!");
+        } else {
+            let data = ds.puzzles.lock().unwrap().get(&PathBuf::from(&key));
+            match data.map(|x| x.load(&mut ds.rt, ds.keybinds.get_keybinds_for_puzzle(&x))) {
+                Some(Ok(puz_data)) => {
+                    self.puzzle = Some(Puzzle::new(puz_data));
+                    self.curr_msg =
+                        format!("Reloaded {name}, but the moves could not be reapplied!");
+                }
+                Some(Err(diag)) => self.curr_msg = diag.msg.to_string(),
+                None => self.curr_msg = format!("{name} is no longer defined!"),
+            }
+        }
+        self.drag = None;
+        self.inspected = None;
+        self.filter_cache = FilterCache::default();
+    }
+    ///the current settings, to be saved for the next session
+    fn settings(&self) -> Settings {
+        Settings {
+            animation_speed: self.animation_speed,
+            outline_width: self.outline_width,
+            view: self.view,
+            cut_on_turn: self.cut_on_turn,
+            log_path: self.log_path.clone(),
+            puzzle: self.puzzle.as_ref().map(Puzzle::to_io_data),
+            color_scheme: self.color_scheme.clone(),
+            show_markings: self.show_markings,
+            puzzle_color_schemes: self.puzzle_color_schemes.clone(),
+            piece_filters: self.piece_filters.clone(),
+            favorites: self.catalog.favorites.clone(),
+            recent: self.catalog.recent.clone(),
+        }
+    }
+    ///the filter preset that is on for the current puzzle, if any
+    fn current_filter(&self) -> Option<&PieceFilter> {
+        let (key, i) = self.active_filter.as_ref()?;
+        if *key != self.puzzle.as_ref()?.data.key() {
+            return None;
+        }
+        self.piece_filters.get(key)?.get(*i)
+    }
+    ///the color scheme for the current puzzle: the scheme for every puzzle, with the puzzle's own scheme on top.
+    ///if markings are shown, colors without a chosen marking are numbered
+    fn scheme(&self) -> ColorScheme {
+        let Some(p) = &self.puzzle else {
+            return self.color_scheme.clone();
+        };
+        let mut scheme = match self.puzzle_color_schemes.get(&p.data.key()) {
+            Some(scheme) => self.color_scheme.layered(scheme),
+            None => self.color_scheme.clone(),
+        };
+        if self.show_markings {
+            for (i, (_, color)) in p.data.used_colors().into_iter().enumerate() {
+                scheme
+                    .markings
+                    .entry(color)
+                    .or_insert(Marking::nth_default(i));
+            }
+        } else {
+            scheme.markings.clear();
+        }
+        scheme
+    }
 }
 impl eframe::App for App {
+    ///save the settings on native when the app is closed
+    #[cfg(not(target_arch = "wasm32"))]
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.autosave();
+    }
+    ///save the settings to local storage on web. this is also called every AUTOSAVE_INTERVAL
+    #[cfg(target_arch = "wasm32")]
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        let settings = self.settings();
+        let string = settings.to_string();
+        if string != self.last_saved {
+            settings.save(storage);
+            self.last_saved = string;
+        }
+    }
+    fn auto_save_interval(&self) -> std::time::Duration {
+        AUTOSAVE_INTERVAL
+    }
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         //run the ui of the program on a central panel
         egui::CentralPanel::default().show(ctx, |ui| {
             let rect = ui.available_rect_before_wrap(); //the space the program has to work with
+            let scheme = self.scheme();
+            //which pieces the filter that is on selects, and how the rest are drawn
+            let filter = self.current_filter().cloned();
+            let selected = match (&self.puzzle, &filter) {
+                (Some(p), Some(filter)) => Some(self.filter_cache.select(p, filter)),
+                _ => None,
+            };
+            let filter = selected.as_deref().zip(filter.map(|x| x.style));
+            //the piece being inspected, and where it belongs. forget it if a turn cut the puzzle, since cutting splits
+            //pieces and moves the ones after them, so the index may now be a different piece
+            if let Some(ref p) = self.puzzle
+                && self.inspected.is_some()
+                && p.pieces.len() != self.inspected_count
+            {
+                self.inspected = None;
+            }
+            //only spherical puzzles can be shown orthographically
+            if self.puzzle.as_ref().is_some_and(|p| !p.data.spherical) {
+                self.view.orthographic = false;
+            }
+            let inspection = match (&self.puzzle, self.inspected) {
+                (Some(p), Some(i)) if self.inspect => Some((i, self.filter_cache.home(p, i))),
+                _ => None,
+            };
             if let Some(ref mut p) = self.puzzle {
                 if !self.preview {
                     //if the puzzle isnt being previewed, render it
-                    if let Err(x) = p.render(
-                        ui,
-                        &rect,
-                        self.outline_width,
-                        self.scale_factor,
-                        self.offset,
-                    ) {
+                    if let Err(x) =
+                        p.render(ui, &rect, self.outline_width, &self.view, &scheme, filter)
+                    {
                         self.curr_msg = x;
                     };
+                    if let Some((i, home)) = inspection {
+                        p.render_inspection(ui, &rect, self.outline_width, &self.view, i, home);
+                    }
                     //if the puzzle is in preview mode, render all of the pieces of the solved state
                 } else {
                     for piece in &p.solved_state {
-                        if let Err(x) = piece.render(
-                            ui,
-                            &rect,
-                            None,
-                            self.outline_width,
-                            self.scale_factor,
-                            self.offset,
-                        ) {
+                        if let Err(x) =
+                            piece.render(ui, &rect, None, self.outline_width, &self.view, &scheme)
+                        {
                             self.curr_msg = x;
                         }
                     }
@@ -122,7 +336,7 @@ impl eframe::App for App {
             }
             //render the data storer panel -- this stores all of the puzzles that you can load
             if let Some(ref mut ds) = self.data_storer {
-                match ds.render_panel(ctx) {
+                match ds.render_panel(ctx, &mut self.catalog) {
                     Err(()) => {
                         self.curr_msg =
                             String::from("Failed to render side panel or failed to create puzzle!")
@@ -131,11 +345,13 @@ impl eframe::App for App {
                         //if a puzzle is returned (a button is clicked), load it
                         match puzzle_data.load(
                             &mut ds.rt,
-                            ds.keybinds.get_keybinds_for_puzzle(&OsString::from(
-                                puzzle_data.path.file_name().unwrap(),
-                            )),
+                            ds.keybinds.get_keybinds_for_puzzle(&puzzle_data),
                         ) {
-                            Ok(puz_data) => self.puzzle = Some(Puzzle::new(puz_data)),
+                            Ok(puz_data) => {
+                                self.catalog.played(&puzzle_data.key());
+                                self.puzzle = Some(Puzzle::new(puz_data));
+                                self.inspected = None;
+                            }
                             Err(diag) => self.curr_msg = diag.msg.to_string(),
                         }
                         // if let Some(kb) = puzzle_data.keybinds
@@ -152,6 +368,17 @@ impl eframe::App for App {
             } else {
                 self.curr_msg = String::from("Error loading data storer!");
             }
+            //eframe only calls save on native with its persistence feature, which is off, so save the settings here
+            #[cfg(not(target_arch = "wasm32"))]
+            if self.last_autosave.elapsed() > AUTOSAVE_INTERVAL {
+                self.autosave();
+            }
+            //reload the definitions and keybinds when they are edited, checking even while nothing else is happening
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+                self.hot_reload();
+                ctx.request_repaint_after(WATCH_INTERVAL);
+            }
             let delta_time = self.last_frame_time.elapsed(); //the time since the last frame
             self.last_frame_time = web_time::Instant::now(); //reset the time tracker
             if let Some(ref mut p) = self.puzzle
@@ -192,11 +419,31 @@ impl eframe::App for App {
                             String::from("Cannot save due to missing puzzle or data storer!")
                         }
                     }
+                    //exporting the puzzle as it is shown, does not work on web
+                    #[cfg(not(target_arch = "wasm32"))]
+                    if ui.add(egui::Button::new("EXPORT SVG")).clicked() {
+                        self.curr_msg = if let Some(ref p) = self.puzzle {
+                            let options = SvgOptions {
+                                outline_width: self.outline_width as f64,
+                                solved: self.preview,
+                                ..Default::default()
+                            };
+                            let path =
+                                PathBuf::from(format!("Puzzles/Exports/{}.svg", self.log_path));
+                            match write_string_to_file(&path, &p.to_svg(&options, &self.scheme())) {
+                                Ok(()) => format!("Exported to {}!", path.to_string_lossy()),
+                                Err(_) => String::from("Error exporting file!"),
+                            }
+                        } else {
+                            String::from("Cannot export due to missing puzzle!")
+                        }
+                    }
                     // //loading, does not work on web
                     #[cfg(not(target_arch = "wasm32"))]
                     if ui.add(egui::Button::new("LOAD LOG")).clicked() {
                         if let Some(ref mut ds) = self.data_storer {
                             self.puzzle = ds.load_save(&self.log_path);
+                            self.inspected = None;
                         } else {
                             self.curr_msg =
                                 String::from("Cannot load log due to missing data storer!")
@@ -218,20 +465,252 @@ impl eframe::App for App {
                     );
                     //rending size (zoom) slider
                     ui.add(
-                        egui::Slider::new(&mut self.scale_factor, (10.0)..=5000.0)
+                        egui::Slider::new(&mut self.view.scale_factor, (10.0)..=5000.0)
                             .text("Rendering Size"),
                     );
                     //panning sliders
-                    ui.add(egui::Slider::new(&mut self.offset.y, (-2.0)..=2.0).text("Move Y"));
-                    ui.add(egui::Slider::new(&mut self.offset.x, (-2.0)..=2.0).text("Move X"));
+                    ui.add(egui::Slider::new(&mut self.view.offset.y, (-2.0)..=2.0).text("Move Y"));
+                    ui.add(egui::Slider::new(&mut self.view.offset.x, (-2.0)..=2.0).text("Move X"));
+                    //rotation slider, shown in degrees
+                    ui.add(
+                        egui::Slider::new(
+                            &mut self.view.rotation,
+                            (-std::f32::consts::PI)..=std::f32::consts::PI,
+                        )
+                        .custom_formatter(|x, _| format!("{:.0}°", x.to_degrees()))
+                        .text("Rotation"),
+                    );
+                    //mirror view toggle
+                    ui.checkbox(&mut self.view.mirrored, "Mirror view?");
+                    //orthographic view toggle, for spherical puzzles
+                    if self.puzzle.as_ref().is_some_and(|p| p.data.spherical) {
+                        ui.checkbox(&mut self.view.orthographic, "Orthographic view?");
+                    }
                     //preview solved state toggle
                     ui.checkbox(&mut self.preview, "Preview solved state?");
+                    //piece inspection toggle
+                    ui.checkbox(&mut self.inspect, "Inspect pieces?");
                     //cut on turn toggle
                     //reset view button
                     if ui.add(egui::Button::new("Reset View")).clicked() {
-                        (self.scale_factor, self.offset) = (SCALE_FACTOR, vec2(0.0, 0.0))
+                        self.view = DEFAULT_VIEW
                     }
                 });
+                //colors menu controls the color scheme
+                let colors_button = default_menu_button("Colors");
+                colors_button.ui(ui, |ui| {
+                    let Some(ref p) = self.puzzle else {
+                        ui.label("No puzzle loaded!");
+                        return;
+                    };
+                    //accessibility toggle
+                    ui.checkbox(&mut self.show_markings, "Show markings?");
+                    //which scheme is being edited
+                    ui.horizontal(|ui| {
+                        ui.radio_value(&mut self.edit_puzzle_scheme, true, "This puzzle");
+                        ui.radio_value(&mut self.edit_puzzle_scheme, false, "All puzzles");
+                    });
+                    let shown = self.scheme();
+                    let scheme = if self.edit_puzzle_scheme {
+                        self.puzzle_color_schemes.entry(p.data.key()).or_default()
+                    } else {
+                        &mut self.color_scheme
+                    };
+                    //a color picker for each color the puzzle uses
+                    for (i, (name, color)) in p.data.used_colors().into_iter().enumerate() {
+                        ui.horizontal(|ui| {
+                            let mut picked = shown.apply(color).to_egui();
+                            if egui::color_picker::color_edit_button_srgba(
+                                ui,
+                                &mut picked,
+                                egui::color_picker::Alpha::Opaque,
+                            )
+                            .changed()
+                            {
+                                scheme.colors.insert(color, Color::from_egui(picked));
+                            }
+                            //the marking drawn over the color, if markings are shown
+                            if self.show_markings
+                                && let Some(marking) = shown.markings.get(&color)
+                            {
+                                let mut picked = marking.clone();
+                                egui::ComboBox::from_id_salt(("marking", color))
+                                    .selected_text(marking_name(&picked))
+                                    .show_ui(ui, |ui| {
+                                        for option in [
+                                            Marking::Plain,
+                                            Marking::Stripes,
+                                            Marking::Dots,
+                                            Marking::Hatching,
+                                            match marking {
+                                                Marking::Glyph(_) => marking.clone(),
+                                                _ => Marking::nth_default(i),
+                                            },
+                                        ] {
+                                            let text = marking_name(&option);
+                                            ui.selectable_value(&mut picked, option, text);
+                                        }
+                                    });
+                                if let Marking::Glyph(glyph) = &mut picked {
+                                    ui.add(egui::TextEdit::singleline(glyph).desired_width(30.0));
+                                }
+                                if picked != *marking {
+                                    scheme.markings.insert(color, picked);
+                                }
+                            }
+                            ui.label(&name);
+                            if (scheme.colors.contains_key(&color)
+                                || scheme.markings.contains_key(&color))
+                                && ui.button("Reset").clicked()
+                            {
+                                scheme.colors.remove(&color);
+                                scheme.markings.remove(&color);
+                            }
+                        });
+                    }
+                    if ui.add(egui::Button::new("Reset Colors")).clicked() {
+                        *scheme = ColorScheme::default();
+                    }
+                });
+                //filters menu chooses which pieces are drawn normally
+                let filters_button = default_menu_button("Filters");
+                filters_button.ui(ui, |ui| {
+                    let Some(ref p) = self.puzzle else {
+                        ui.label("No puzzle loaded!");
+                        return;
+                    };
+                    let key = p.data.key();
+                    let filters = self.piece_filters.entry(key.clone()).or_default();
+                    //which filter is on
+                    let mut on = match &self.active_filter {
+                        Some((k, i)) if *k == key => Some(*i),
+                        _ => None,
+                    };
+                    ui.radio_value(&mut on, None, "No filter");
+                    for (i, filter) in filters.iter().enumerate() {
+                        let text = match filter.key {
+                            Some(bind) => format!("{} ({})", filter.name, bind.name()),
+                            None => filter.name.clone(),
+                        };
+                        ui.radio_value(&mut on, Some(i), text);
+                    }
+                    if ui.add(egui::Button::new("New Filter")).clicked() {
+                        filters.push(PieceFilter::new(&format!("Filter {}", filters.len() + 1)));
+                        on = Some(filters.len() - 1);
+                    }
+                    //edit the filter that is on
+                    if let Some(i) = on
+                        && let Some(filter) = filters.get_mut(i)
+                    {
+                        ui.separator();
+                        ui.add(egui::TextEdit::singleline(&mut filter.name));
+                        ui.horizontal(|ui| {
+                            ui.label("Other pieces:");
+                            ui.radio_value(
+                                &mut filter.style,
+                                FilterStyle::Translucent,
+                                "Translucent",
+                            );
+                            ui.radio_value(&mut filter.style, FilterStyle::Gray, "Gray");
+                            ui.radio_value(&mut filter.style, FilterStyle::Hidden, "Hidden");
+                        });
+                        egui::ComboBox::from_label("Toggle key")
+                            .selected_text(filter.key.map_or("None", |x| x.name()))
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut filter.key, None, "None");
+                                for bind in egui::Key::ALL {
+                                    ui.selectable_value(&mut filter.key, Some(*bind), bind.name());
+                                }
+                            });
+                        //select by color. no colors checked means every color
+                        ui.label("Colors:");
+                        for (name, color) in p.data.used_colors() {
+                            let mut checked = filter.colors.contains(&color);
+                            if ui.checkbox(&mut checked, name).changed() {
+                                filter.colors.retain(|x| *x != color);
+                                if checked {
+                                    filter.colors.push(color);
+                                }
+                            }
+                        }
+                        //select by where the pieces belong, inside or outside of each turn circle
+                        ui.label("Region (where the pieces belong):");
+                        if filter.region.0.len() != 1 {
+                            //regions made of several parts can only be written in the settings file
+                            ui.label(format!("({} parts)", filter.region.0.len()));
+                            if ui.add(egui::Button::new("Clear Region")).clicked() {
+                                filter.region = Region::everywhere();
+                            }
+                        } else {
+                            let part = &mut filter.region.0[0];
+                            let mut ids = p.turns.keys().collect::<Vec<&String>>();
+                            ids.sort();
+                            let mut circles: Vec<Circle> = Vec::new();
+                            for id in ids {
+                                let circle = p.turns[id].turn.circle;
+                                if circles.iter().any(|x| x.approx_eq(&circle, PRECISION)) {
+                                    continue;
+                                }
+                                circles.push(circle);
+                                let position = part
+                                    .iter()
+                                    .position(|x| x.circ.approx_eq(&circle, PRECISION));
+                                let old = position.map(|x| part[x].ori);
+                                let mut ori = old;
+                                ui.horizontal(|ui| {
+                                    egui::ComboBox::from_id_salt(("region", id))
+                                        .selected_text(region_name(ori))
+                                        .show_ui(ui, |ui| {
+                                            for option in [
+                                                None,
+                                                Some(Contains::Inside),
+                                                Some(Contains::Outside),
+                                            ] {
+                                                ui.selectable_value(
+                                                    &mut ori,
+                                                    option,
+                                                    region_name(option),
+                                                );
+                                            }
+                                        });
+                                    ui.label(id);
+                                });
+                                if ori != old {
+                                    if let Some(x) = position {
+                                        part.remove(x);
+                                    }
+                                    if let Some(ori) = ori {
+                                        part.push(OrientedCircle { circ: circle, ori });
+                                    }
+                                }
+                            }
+                        }
+                        //select by orbit. no orbits checked means every orbit
+                        ui.label("Orbits:");
+                        let orbits = self.filter_cache.orbits(p);
+                        for orbit in 0..orbits.iter().max().map_or(0, |x| x + 1) {
+                            let size = orbits.iter().filter(|x| **x == orbit).count();
+                            let mut checked = filter.orbits.contains(&orbit);
+                            if ui
+                                .checkbox(
+                                    &mut checked,
+                                    format!("Orbit {} ({size} pieces)", orbit + 1),
+                                )
+                                .changed()
+                            {
+                                filter.orbits.retain(|x| *x != orbit);
+                                if checked {
+                                    filter.orbits.push(orbit);
+                                }
+                            }
+                        }
+                        if ui.add(egui::Button::new("Delete Filter")).clicked() {
+                            filters.remove(i);
+                            on = None;
+                        }
+                    }
+                    self.active_filter = on.map(|i| (key, i));
+                });
                 //scramble menu controls scrambling
                 let scramble_button = default_menu_button("Scramble");
                 scramble_button.ui(ui, |ui| {
@@ -268,26 +747,54 @@ impl eframe::App for App {
                         p.check();
                     }
                 });
-                //credits menu displays credits (bugged?)
+                //credits menu displays the credits and statistics about the puzzles
                 let credits_button = default_menu_button("Credits");
                 credits_button.ui(ui, |ui| {
                     //display the credits
                     ui.label(CREDITS);
                     ui.separator();
-                    //display the puzzle contributors
-                    // ui.label(RichText::new("Top puzzle contributors:").color(egui::Color32::WHITE));
-                    // match self
-                    //     .data_storer
-                    //     .get_top_authors::<{ crate::ui::data_storer::TOP }>()
-                    // {
-                    //     //add the labels for the top 5 puzzle contributors
-                    //     Ok(top) => {
-                    //         for t in top {
-                    //             ui.label(format!("{}: {}", t.0, t.1));
-                    //         }
-                    //     }
-                    //     Err(_) => {}
-                    // }
+                    //display the catalog statistics, counting the pieces on another thread so the menu doesn't freeze
+                    let Some(ds) = &mut self.data_storer else {
+                        return;
+                    };
+                    if ds.count_in_background(DEF_PATH) {
+                        ui.ctx().request_repaint_after(COUNT_CHECK_INTERVAL);
+                    }
+                    let stats = CatalogStats::new(&ds.puzzles.lock().unwrap().defs(), &ds.counts);
+                    ui.label(format!(
+                        "{} puzzles by {} authors, with {} pieces in all",
+                        stats.puzzles,
+                        stats.authors.len(),
+                        stats.pieces
+                    ));
+                    #[cfg(not(target_arch = "wasm32"))]
+                    if stats.uncounted > 0 {
+                        ui.label(format!("Counting pieces... ({} left)", stats.uncounted));
+                    }
+                    #[cfg(target_arch = "wasm32")]
+                    ui.label("Pieces can't be counted on web");
+                    ui.label(RichText::new("Top puzzle contributors:").color(egui::Color32::WHITE));
+                    for (author, count) in stats.top_authors(TOP) {
+                        ui.label(format!("{author}: {count}"));
+                    }
+                    ui.collapsing("Most complex puzzles", |ui| {
+                        for (key, counts) in stats.most_complex(TOP) {
+                            ui.label(format!(
+                                "{key}: {} pieces, {} turns",
+                                counts.pieces, counts.turns
+                            ));
+                        }
+                    });
+                    ui.collapsing("Puzzles per folder", |ui| {
+                        for (folder, count) in stats.folders() {
+                            ui.label(format!("{folder}: {count}"));
+                        }
+                    });
+                    ui.collapsing("Puzzles per number of circles", |ui| {
+                        for (circles, count) in stats.circles() {
+                            ui.label(format!("{circles} circles: {count}"));
+                        }
+                    });
                 });
             });
             //UI Section: display puzzle info
@@ -296,9 +803,80 @@ impl eframe::App for App {
                     .default_pos((10.0, 40.0))
                     .auto_sized()
                     .show(ctx, |ui| {
+                        ui.set_max_width(300.0);
+                        let metadata = &p.data.metadata;
                         ui.label(String::from("Name: ") + &p.name);
+                        if !metadata.aliases.is_empty() {
+                            ui.label(
+                                String::from("Also known as: ") + &metadata.aliases.join(", "),
+                            );
+                        }
                         ui.label(String::from("Authors: ") + &p.authors.join(", "));
+                        if !metadata.created.is_empty() {
+                            ui.label(String::from("Created: ") + &metadata.created);
+                        }
+                        if let Some(difficulty) = metadata.difficulty {
+                            //filled stars for the difficulty, and empty ones for the rest
+                            ui.label(
+                                String::from("Difficulty: ")
+                                    + &"★".repeat(difficulty)
+                                    + &"☆".repeat(MAX_DIFFICULTY - difficulty),
+                            );
+                        }
+                        if !metadata.tags.is_empty() {
+                            ui.label(String::from("Tags: ") + &metadata.tags.join(", "));
+                        }
                         ui.label(p.pieces.len().to_string() + " pieces");
+                        if !metadata.description.is_empty() {
+                            ui.separator();
+                            ui.label(&metadata.description);
+                        }
+                        if !metadata.inspired_by.is_empty() {
+                            ui.separator();
+                            ui.label("Inspired by:");
+                            //links are clickable, and anything else is just named
+                            for inspiration in &metadata.inspired_by {
+                                if inspiration.starts_with("http://")
+                                    || inspiration.starts_with("https://")
+                                {
+                                    ui.hyperlink(inspiration);
+                                } else {
+                                    ui.label(inspiration);
+                                }
+                            }
+                        }
+                    });
+            }
+            //UI Section: display info about the piece being inspected
+            if let Some(ref p) = self.puzzle
+                && let Some((i, home)) = inspection
+                && let Some(info) = p.piece_info(i, home)
+            {
+                let list = |ids: &[String]| {
+                    if ids.is_empty() {
+                        String::from("none")
+                    } else {
+                        ids.join(", ")
+                    }
+                };
+                Window::new("Piece Info")
+                    .default_pos((10.0, 140.0))
+                    .auto_sized()
+                    .show(ctx, |ui| {
+                        ui.label(String::from("Color: ") + &info.color);
+                        ui.label(info.arcs.to_string() + " arcs");
+                        if info.components > 1 {
+                            ui.label(info.components.to_string() + " parts");
+                        }
+                        ui.label(String::from("Moved by: ") + &list(&info.moved_by));
+                        if !info.blocked.is_empty() {
+                            ui.label(String::from("Blocks: ") + &list(&info.blocked));
+                        }
+                        ui.label(match (info.home, info.in_place) {
+                            (None, _) => "Home not found",
+                            (Some(_), true) => "In place",
+                            (Some(_), false) => "Out of place",
+                        });
                     });
             }
             //UI Section: Bottom left area
@@ -321,6 +899,12 @@ impl eframe::App for App {
                         if !self.curr_msg.is_empty() {
                             ui.label(&self.curr_msg);
                         }
+                        //display any problems found in the keybind config
+                        if let Some(ref ds) = self.data_storer {
+                            for diag in &ds.keybind_diagnostics {
+                                ui.label(diag.to_string());
+                            }
+                        }
                     });
             });
             //gets the rect for interaction with the puzzle (so that ui elements like buttons dont conflict with puzzle input)
@@ -352,19 +936,23 @@ impl eframe::App for App {
                     })
                     .sum::<i32>()
             });
-            //if the puzzle is clicked and not in preview mode
+            //in inspection mode, clicking a piece inspects it (and clicking nothing stops inspecting)
+            if r.clicked()
+                && self.inspect
+                && !self.preview
+                && let Some(pointer) = r.interact_pointer_pos()
+                && let Some(ref p) = self.puzzle
+            {
+                self.inspected = p.piece_under(&rect, pointer, &self.view);
+                self.inspected_count = p.pieces.len();
+            }
+            //if the puzzle is clicked and not in preview or inspection mode
             if r.clicked()
                 && !self.preview
+                && !self.inspect
                 && let Some(pointer) = r.interact_pointer_pos()
                 && let Some(ref mut p) = self.puzzle
-                && let Err(x) = p.process_click(
-                    &rect,
-                    pointer,
-                    true,
-                    self.scale_factor,
-                    self.offset,
-                    self.cut_on_turn,
-                )
+                && let Err(x) = p.process_click(&rect, pointer, true, &self.view, self.cut_on_turn)
             {
                 {
                     self.curr_msg = x;
@@ -373,20 +961,111 @@ impl eframe::App for App {
             //the same input parsing but for the right click
             if r.clicked_by(egui::PointerButton::Secondary)
                 && !self.preview
+                && !self.inspect
                 && let Some(pointer) = r.interact_pointer_pos()
                 && let Some(ref mut p) = self.puzzle
-                && let Err(x) = p.process_click(
-                    &rect,
-                    pointer,
-                    false,
-                    self.scale_factor,
-                    self.offset,
-                    self.cut_on_turn,
-                )
+                && let Err(x) = p.process_click(&rect, pointer, false, &self.view, self.cut_on_turn)
+            {
+                self.curr_msg = x;
+            }
+            //dragging with the left mouse button turns the circle the drag started in, following the pointer around its center
+            if r.drag_started_by(egui::PointerButton::Primary)
+                && !self.preview
+                && !self.inspect
+                && !ui.input(|i| i.modifiers.shift_only())
+                && let Some(origin) = ui.input(|i| i.pointer.press_origin())
+                && let Some(ref p) = self.puzzle
+                && let Some(id) = p.turn_id_at(&rect, origin, &self.view)
+                && let Some(angle) = p.pointer_angle(&id, &rect, origin, &self.view)
+            {
+                self.drag = Some(DragTurn {
+                    id,
+                    last_angle: angle,
+                    swept: 0.0,
+                    touch: false,
+                });
+            }
+            //two finger gestures: pinching zooms, moving pans, and twisting turns the circle the gesture started on
+            let multi_touch = ui.input(|i| i.multi_touch());
+            if multi_touch.is_some() && self.drag.as_ref().is_some_and(|x| !x.touch) {
+                //a second finger landed during a one finger drag, so cancel it and let it animate back
+                self.drag = None;
+            }
+            match self.touch.update(multi_touch) {
+                Some(TouchAction::Camera { zoom, pan }) => {
+                    self.view.scale_factor = (self.view.scale_factor * zoom).clamp(10.0, 5000.0);
+                    self.view.offset += vec2(
+                        pan.x / self.view.scale_factor,
+                        -(pan.y / self.view.scale_factor),
+                    );
+                }
+                Some(TouchAction::StartTurn { pos, rotation }) => {
+                    self.drag = if !self.preview
+                        && !self.inspect
+                        && let Some(ref p) = self.puzzle
+                        && let Some(id) = p.turn_id_at(&rect, pos, &self.view)
+                    {
+                        Some(DragTurn {
+                            id,
+                            last_angle: 0.0,
+                            swept: self.view.puzzle_angle(rotation) as f64,
+                            touch: true,
+                        })
+                    } else {
+                        None
+                    };
+                }
+                Some(TouchAction::Turn(rotation)) => {
+                    if let Some(ref mut drag) = self.drag {
+                        drag.swept += self.view.puzzle_angle(rotation) as f64;
+                    }
+                }
+                Some(TouchAction::EndTurn) => {
+                    if let Some(drag) = self.drag.take()
+                        && let Some(ref mut p) = self.puzzle
+                        && let Err(x) = p.finish_drag(&drag.id, drag.swept, self.cut_on_turn)
+                    {
+                        self.curr_msg = x;
+                    }
+                }
+                None => {}
+            }
+            //while dragging, add up the angle swept by the pointer and show the partial turn
+            if let Some(ref mut drag) = self.drag
+                && let Some(ref mut p) = self.puzzle
+            {
+                if !drag.touch
+                    && r.dragged_by(egui::PointerButton::Primary)
+                    && let Some(pointer) = r.interact_pointer_pos()
+                    && let Some(angle) = p.pointer_angle(&drag.id, &rect, pointer, &self.view)
+                {
+                    //take the change in angle the short way around, so that crossing the negative x axis doesn't jump
+                    drag.swept += (angle - drag.last_angle + PI).rem_euclid(2.0 * PI) - PI;
+                    drag.last_angle = angle;
+                }
+                p.preview_drag(&drag.id, drag.swept);
+            }
+            //when the drag is released, snap to the nearest multiple of the turn
+            if r.drag_stopped_by(egui::PointerButton::Primary)
+                && self.drag.as_ref().is_some_and(|x| !x.touch)
+                && let Some(drag) = self.drag.take()
+                && let Some(ref mut p) = self.puzzle
+                && let Err(x) = p.finish_drag(&drag.id, drag.swept, self.cut_on_turn)
+            {
+                self.curr_msg = x;
+            }
+            //on touch screens, a tap turns like a left click and a long press turns like a right click
+            if r.long_touched()
+                && !self.preview
+                && !self.inspect
+                && let Some(pointer) = r.interact_pointer_pos()
+                && let Some(ref mut p) = self.puzzle
+                && let Err(x) = p.process_click(&rect, pointer, false, &self.view, self.cut_on_turn)
             {
                 self.curr_msg = x;
             }
             if ui.input(|i: &InputState| i.key_pressed(egui::Key::Z))
+                && ui.ctx().memory(|x| x.focused().is_none())
                 && let Some(ref mut p) = self.puzzle
             {
                 let _ = p.undo();
@@ -406,6 +1085,22 @@ impl eframe::App for App {
                     } = event
                     {
                         let b = if let Some(p) = physical_key { p } else { key };
+                        //filter keys turn their filter on and off instead of turning
+                        let puzzle_key = p.data.key();
+                        if pressed
+                            && let Some(i) = self
+                                .piece_filters
+                                .get(&puzzle_key)
+                                .and_then(|x| x.iter().position(|x| x.key == Some(b)))
+                        {
+                            let toggled = Some((puzzle_key, i));
+                            self.active_filter = if self.active_filter == toggled {
+                                None
+                            } else {
+                                toggled
+                            };
+                            continue;
+                        }
                         if pressed
                             && let Some((t, m)) = p.keybinds.get(&b).cloned()
                             && p.turns.contains_key(&t)
@@ -423,13 +1118,13 @@ impl eframe::App for App {
                 && !self.preview
                 && let Some(pointer) = r.hover_pos()
             {
-                let hovered_circle = p.get_hovered(&rect, pointer, self.scale_factor, self.offset);
+                let hovered_circle = p.get_hovered(&rect, pointer, &self.view);
                 //get the hovered circle (turn circle)
                 if let Err(x) = &hovered_circle {
                     self.curr_msg = x.clone();
                 }
                 if let Ok(Some(real_circle)) = hovered_circle {
-                    draw_circle(real_circle, ui, &rect, self.scale_factor, self.offset);
+                    draw_circle(real_circle, ui, &rect, &self.view);
                 } //if a circle is hovered, highlight its border
                 //if a circle is being hovered and the scroll wheel is being used, parse the scroll like a click
                 //if the middle mouse button is pressed, or the control button is pressed, dont parse this input as these are camera commands
@@ -437,15 +1132,10 @@ impl eframe::App for App {
                     && !r.dragged_by(egui::PointerButton::Middle)
                     && !ui.input(|i| i.modifiers.command_only())
                     && !self.preview
+                    && !self.inspect
                     && let Some(pointer) = r.hover_pos()
-                    && let Err(x) = p.process_click(
-                        &rect,
-                        pointer,
-                        scroll > 0,
-                        self.scale_factor,
-                        self.offset,
-                        self.cut_on_turn,
-                    )
+                    && let Err(x) =
+                        p.process_click(&rect, pointer, scroll > 0, &self.view, self.cut_on_turn)
                 {
                     self.curr_msg = x;
                 }
@@ -453,12 +1143,36 @@ impl eframe::App for App {
             //if the middle mouse button is being pressed, pan the camera
             if r.dragged_by(egui::PointerButton::Middle) {
                 let delta = r.drag_delta();
-                let good_delta = vec2(delta.x / self.scale_factor, -(delta.y / self.scale_factor));
-                self.offset += good_delta;
+                let good_delta = vec2(
+                    delta.x / self.view.scale_factor,
+                    -(delta.y / self.view.scale_factor),
+                );
+                self.view.offset += good_delta;
+            }
+            //if shift dragging, rotate the camera around the center of the screen
+            if r.dragged_by(egui::PointerButton::Primary)
+                && ui.input(|i| i.modifiers.shift_only())
+                && let Some(pointer) = r.interact_pointer_pos()
+            {
+                let (curr, prev) = (
+                    pointer - rect.center(),
+                    pointer - r.drag_delta() - rect.center(),
+                );
+                //the screen is flipped vertically, so a clockwise drag on screen is a negative angle
+                self.view.rotate(prev.angle() - curr.angle());
+            }
+            //the bracket keys rotate the camera in steps
+            if ui.ctx().memory(|x| x.focused().is_none()) {
+                if ui.input(|i| i.key_pressed(egui::Key::OpenBracket)) {
+                    self.view.rotate(ROTATION_STEP);
+                }
+                if ui.input(|i| i.key_pressed(egui::Key::CloseBracket)) {
+                    self.view.rotate(-ROTATION_STEP);
+                }
             }
             //if ctrl scrolling, zoom
             if ui.input(|i| i.modifiers.command_only()) && scroll != 0 {
-                self.scale_factor += 10.0 * scroll as f32;
+                self.view.scale_factor += 10.0 * scroll as f32;
             }
         });
     }
@@ -470,3 +1184,23 @@ fn default_menu_button<'a>(text: &'a str) -> egui::containers::menu::MenuButton<
     let config = egui::containers::menu::MenuConfig::new();
     button.config(config.close_behavior(PopupCloseBehavior::CloseOnClickOutside))
 }
+
+///the name of a choice for the region of a filter, for the filters menu
+fn region_name(ori: Option<Contains>) -> &'static str {
+    match ori {
+        None => "Anywhere",
+        Some(Contains::Outside) => "Outside",
+        Some(_) => "Inside",
+    }
+}
+
+///the name of a kind of marking, for the colors menu
+fn marking_name(marking: &Marking) -> &'static str {
+    match marking {
+        Marking::Plain => "Plain",
+        Marking::Stripes => "Stripes",
+        Marking::Dots => "Dots",
+        Marking::Hatching => "Hatching",
+        Marking::Glyph(_) => "Glyph",
+    }
+}
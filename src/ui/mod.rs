@@ -1,3 +1,5 @@
 pub mod app;
 pub mod keybinds;
 pub mod render;
+pub mod settings;
+pub mod touch;
@@ -4,11 +4,18 @@ use crate::complex::c64::C64;
 use crate::complex::complex_circle::Circle;
 use crate::complex::complex_circle::Contains;
 use crate::complex::point::Point;
+use crate::complex::sphere::SpherePoint;
+use crate::complex::vector::Vector;
+use crate::hps::data_storer::catalog::{COUNT_CHECK_INTERVAL, Catalog, CatalogSort};
 use crate::hps::data_storer::data_storer::DataStorer;
 use crate::hps::data_storer::data_storer::PuzzleLoadingData;
+use crate::hps::data_storer::data_storer::Thumbnails;
 use crate::hps::data_storer::def_entry::DefEntry;
-use crate::puzzle::color::Color;
+use crate::hps::data_storer::io::read_file_to_bytes;
+use crate::puzzle::color::{Color, ColorScheme, Marking};
+use crate::puzzle::filter::FilterStyle;
 use crate::puzzle::puzzle::*;
+use crate::puzzle::raster::Image;
 use crate::puzzle::render_piece::RenderPiece;
 use crate::puzzle::render_piece::Triangulation;
 use crate::puzzle::turn::*;
@@ -22,6 +29,8 @@ use egui::{
     pos2,
 };
 use std::cmp::*;
+use std::collections::HashMap;
+use std::f32::consts::PI;
 use std::ffi::OsString;
 
 pub struct RenderingCircle {
@@ -29,94 +38,370 @@ pub struct RenderingCircle {
     pub rad: f32,
 }
 
+#[derive(Debug, Clone, Copy)]
+///the camera settings used to place the puzzle on the screen
+pub struct View {
+    pub scale_factor: f32,  //the scale factor (zoom)
+    pub offset: Vec2,       //the offset of the puzzle from the center of the screen (pan)
+    pub rotation: f32, //the angle the puzzle is rotated by on the screen, counterclockwise, in radians
+    pub mirrored: bool, //whether the puzzle is shown left to right mirrored, before it is rotated
+    pub orthographic: bool, //whether a spherical puzzle is shown as its two hemispheres, instead of its projection
+}
+
+impl View {
+    ///rotate the view by an angle, keeping the rotation between -pi and pi
+    pub fn rotate(&mut self, angle: f32) {
+        self.rotation = (self.rotation + angle + PI).rem_euclid(2.0 * PI) - PI;
+    }
+    ///the angle swept in the puzzle by an angle swept on the screen. the screen is flipped vertically, so this is
+    ///negated, unless the view is mirrored too
+    pub fn puzzle_angle(&self, screen_angle: f32) -> f32 {
+        if self.mirrored {
+            screen_angle
+        } else {
+            -screen_angle
+        }
+    }
+}
+
 ///the default rendering color
 ///the color of the outlines
 const OUTLINE_COLOR: Color32 = Color32::BLACK;
+///the distance between the lines or dots of a marking pattern, in points
+const PATTERN_SPACING: f32 = 10.0;
+///the size of marking glyphs
+const GLYPH_SIZE: f32 = 18.0;
+///the size thumbnails are shown at in the puzzle list
+const THUMBNAIL_SIZE: f32 = 48.0;
+///the color of pieces grayed out by a filter
+const FILTERED_GRAY: Color = Color::rgb(90, 90, 90);
+///how many times more transparent pieces made translucent by a filter are
+const TRANSLUCENCY: u8 = 5;
+///the color of the outline of the piece being inspected
+const INSPECTED_COLOR: Color32 = Color32::YELLOW;
+///the color of the ghost outline drawn where the piece being inspected belongs (translucent white, premultiplied)
+const GHOST_COLOR: Color32 = Color32::from_rgba_premultiplied(180, 180, 180, 180);
+///the length of the dashes (and the gaps between them) of the ghost outline, in points
+const GHOST_DASH: f32 = 8.0;
+///how far the centers of the hemispheres are from the origin in the orthographic view, so that there is a gap between
+///them
+const HEMISPHERE_OFFSET: f64 = 1.1;
+///the longest a segment can be on the unit sphere to be drawn straight in the orthographic view
+const MAX_CHORD: f64 = 0.05;
+///how many times a shape can be cut in half to be drawn in the orthographic view
+const MAX_SPLITS: u32 = 16;
+///the number of segments the hovered circle is drawn with in the orthographic view
+const CIRCLE_SEGMENTS: usize = 128;
 
 impl Color {
     pub fn to_egui(&self) -> Color32 {
         match self {
-            Color::Red => Color32::RED,
-            Color::Green => Color32::GREEN,
-            Color::Blue => Color32::BLUE,
-            Color::Yellow => Color32::YELLOW,
-            Color::Purple => Color32::PURPLE,
-            Color::Gray => Color32::GRAY,
-            Color::Black => Color32::BLACK,
-            Color::Brown => Color32::BROWN,
-            Color::Cyan => Color32::CYAN,
-            Color::White => Color32::WHITE,
-            Color::DarkBlue => Color32::DARK_BLUE,
-            Color::DarkGreen => Color32::DARK_GREEN,
-            Color::DarkGray => Color32::DARK_GRAY,
-            Color::DarkRed => Color32::DARK_RED,
-            Color::LightBlue => Color32::LIGHT_BLUE,
-            Color::LightGray => Color32::LIGHT_GRAY,
-            Color::LightGreen => Color32::LIGHT_GREEN,
-            Color::LightYellow => Color32::LIGHT_YELLOW,
-            Color::LightRed => Color32::LIGHT_RED,
-            Color::Khaki => Color32::KHAKI,
-            Color::Gold => Color32::GOLD,
-            Color::Magenta => Color32::MAGENTA,
-            Color::Orange => Color32::ORANGE,
+            Color::Rgba([r, g, b, a]) => Color32::from_rgba_unmultiplied(*r, *g, *b, *a),
             Color::None => Color32::GRAY,
         }
     }
+    pub fn from_egui(color: Color32) -> Self {
+        Color::Rgba(color.to_srgba_unmultiplied())
+    }
 }
 
 ///draws a the circumference of a circle given the coordinates
-pub fn draw_circle(real_circle: Circle, ui: &mut Ui, rect: &Rect, scale_factor: f32, offset: Vec2) {
-    {
+pub fn draw_circle(real_circle: Circle, ui: &mut Ui, rect: &Rect, view: &View) {
+    if view.orthographic {
+        //circles aren't circles on the screen, so follow them around
+        let points = (0..=CIRCLE_SEGMENTS)
+            .map(|i| {
+                real_circle.center
+                    + Vector(
+                        real_circle.r()
+                            * C64::from_angle(
+                                2.0 * f64::consts::PI * i as f64 / CIRCLE_SEGMENTS as f64,
+                            ),
+                    )
+            })
+            .collect::<Vec<_>>();
+        for line in screen_lines(&points, rect, view) {
+            ui.painter()
+                .add(PathShape::line(line, Stroke::new(10.0, Color32::WHITE)));
+        }
+    } else {
         ui.painter().circle_stroke(
-            real_circle.center.to_pos2(rect, scale_factor, offset),
-            real_circle.r() as f32 * scale_factor * (rect.width() / 1920.0),
+            real_circle.center.to_pos2(rect, view),
+            real_circle.r() as f32 * view.scale_factor * (rect.width() / 1920.0),
             (10.0, Color32::WHITE),
         );
     }
 }
 
+///where the orthographic view draws a point: on the left, the northern hemisphere seen from above, and on the right,
+///the southern hemisphere seen from below (as if the sphere was turned over left to right)
+fn orthographic(p: SpherePoint, south: bool) -> C64 {
+    if south {
+        C64 {
+            re: HEMISPHERE_OFFSET - p.x,
+            im: p.y,
+        }
+    } else {
+        C64 {
+            re: p.x - HEMISPHERE_OFFSET,
+            im: p.y,
+        }
+    }
+}
+
+///the point the orthographic view draws at a position, on the nearer hemisphere. positions off of the hemispheres are
+///moved to their edges. returns None for the south pole, which is infinitely far away in the puzzle
+fn from_orthographic(pos: C64) -> Option<Point> {
+    let south = pos.re > 0.0;
+    let local = C64 {
+        re: if south {
+            HEMISPHERE_OFFSET - pos.re
+        } else {
+            pos.re + HEMISPHERE_OFFSET
+        },
+        im: pos.im,
+    };
+    let local = if local.mag_sq() > 1.0 {
+        (1.0 / local.mag()) * local
+    } else {
+        local
+    };
+    let z = (1.0 - local.mag_sq()).max(0.0).sqrt();
+    SpherePoint {
+        x: local.re,
+        y: local.im,
+        z: if south { -z } else { z },
+    }
+    .to_point()
+}
+
+///the point halfway between two points of the plane
+fn midpoint(a: Point, b: Point) -> Point {
+    Point(C64 {
+        re: (a.0.re + b.0.re) / 2.0,
+        im: (a.0.im + b.0.im) / 2.0,
+    })
+}
+
+///the point where the segment between two points of the sphere crosses the equator, moved onto the sphere
+fn equator_crossing(a: SpherePoint, b: SpherePoint) -> SpherePoint {
+    let t = a.z / (a.z - b.z);
+    SpherePoint::from_direction(a.x + t * (b.x - a.x), a.y + t * (b.y - a.y), 0.0).unwrap_or(a)
+}
+
+///whether a point of the sphere is on a hemisphere (the southern one if south is true). points on the equator are on
+///both
+fn on_hemisphere(p: SpherePoint, south: bool) -> bool {
+    if south { p.z <= 0.0 } else { p.z >= 0.0 }
+}
+
+///split a triangle of the plane in half along its longest edge, until its edges are short enough on the sphere for it
+///to be drawn flat in the orthographic view
+fn subdivide_triangle(triangle: [Point; 3], splits: u32, triangles: &mut Vec<[SpherePoint; 3]>) {
+    let sphere = triangle.map(SpherePoint::from_point);
+    let lengths = [0, 1, 2].map(|i| sphere[i].dist(&sphere[(i + 1) % 3]));
+    let longest = (0..3)
+        .max_by(|i, j| lengths[*i].total_cmp(&lengths[*j]))
+        .unwrap();
+    if splits == 0 || lengths[longest] <= MAX_CHORD {
+        triangles.push(sphere);
+        return;
+    }
+    let [a, b, c] = [0, 1, 2].map(|i| triangle[(longest + i) % 3]);
+    let mid = midpoint(a, b);
+    subdivide_triangle([a, mid, c], splits - 1, triangles);
+    subdivide_triangle([mid, b, c], splits - 1, triangles);
+}
+
+///split a segment of the plane in half, until its parts are short enough on the sphere to be drawn straight in the
+///orthographic view. the points after the start of the segment are added to the path
+fn subdivide_segment(a: Point, b: Point, splits: u32, path: &mut Vec<SpherePoint>) {
+    if splits == 0 || SpherePoint::from_point(a).dist(&SpherePoint::from_point(b)) <= MAX_CHORD {
+        path.push(SpherePoint::from_point(b));
+        return;
+    }
+    let mid = midpoint(a, b);
+    subdivide_segment(a, mid, splits - 1, path);
+    subdivide_segment(mid, b, splits - 1, path);
+}
+
+///the part of a polygon of the sphere on a hemisphere, cut off at the equator
+fn clip_to_hemisphere(polygon: &[SpherePoint], south: bool) -> Vec<SpherePoint> {
+    let mut clipped = Vec::new();
+    for (i, a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+        if on_hemisphere(*a, south) {
+            clipped.push(*a);
+        }
+        if (a.z > 0.0 && b.z < 0.0) || (a.z < 0.0 && b.z > 0.0) {
+            clipped.push(equator_crossing(*a, b));
+        }
+    }
+    clipped
+}
+
+///the triangles to draw a triangle of the plane as. in the orthographic view, the triangle is cut into small ones,
+///which are cut at the equator and drawn on their own hemispheres
+pub fn screen_triangles(triangle: [Point; 3], rect: &Rect, view: &View) -> Vec<[Pos2; 3]> {
+    if !view.orthographic {
+        return vec![triangle.map(|x| x.to_pos2(rect, view))];
+    }
+    let mut small = Vec::new();
+    subdivide_triangle(triangle, MAX_SPLITS, &mut small);
+    let mut triangles = Vec::new();
+    for south in [false, true] {
+        for t in &small {
+            let polygon = clip_to_hemisphere(t, south)
+                .into_iter()
+                .map(|x| plane_to_pos2(orthographic(x, south), rect, view))
+                .collect::<Vec<Pos2>>();
+            //the clipped triangle is convex, so it can be drawn as a fan
+            for i in 2..polygon.len() {
+                triangles.push([polygon[0], polygon[i - 1], polygon[i]]);
+            }
+        }
+    }
+    triangles
+}
+
+///the lines to draw along a path. in the orthographic view, the path is cut into short segments, which are cut at the
+///equator and drawn on their own hemispheres
+fn screen_lines(points: &[Point], rect: &Rect, view: &View) -> Vec<Vec<Pos2>> {
+    if !view.orthographic {
+        return vec![points.iter().map(|x| x.to_pos2(rect, view)).collect()];
+    }
+    let Some(first) = points.first() else {
+        return Vec::new();
+    };
+    let mut path = vec![SpherePoint::from_point(*first)];
+    for segment in points.windows(2) {
+        subdivide_segment(segment[0], segment[1], MAX_SPLITS, &mut path);
+    }
+    let mut lines = Vec::new();
+    for south in [false, true] {
+        let to_pos2 = |x: SpherePoint| plane_to_pos2(orthographic(x, south), rect, view);
+        let mut line = Vec::new();
+        for segment in path.windows(2) {
+            let [a, b] = [segment[0], segment[1]];
+            match (on_hemisphere(a, south), on_hemisphere(b, south)) {
+                (true, true) => {
+                    if line.is_empty() {
+                        line.push(to_pos2(a));
+                    }
+                    line.push(to_pos2(b));
+                }
+                (true, false) => {
+                    if line.is_empty() {
+                        line.push(to_pos2(a));
+                    }
+                    line.push(to_pos2(equator_crossing(a, b)));
+                    lines.push(std::mem::take(&mut line));
+                }
+                (false, true) => {
+                    line.push(to_pos2(equator_crossing(a, b)));
+                    line.push(to_pos2(b));
+                }
+                (false, false) => {}
+            }
+        }
+        if line.len() > 1 {
+            lines.push(line);
+        }
+    }
+    lines
+}
+
+///translates from the plane the view is drawn in to egui coords. the point is mirrored if the view is, rotated about
+///the origin, then panned and scaled
+fn plane_to_pos2(plane: C64, rect: &Rect, view: &View) -> Pos2 {
+    let mirrored = if view.mirrored {
+        C64 {
+            re: -plane.re,
+            im: plane.im,
+        }
+    } else {
+        plane
+    };
+    let rotated = C64::from_angle(view.rotation as f64) * mirrored;
+    pos2(
+        (rotated.re as f32 + view.offset.x) * (view.scale_factor * rect.width() / 1920.0)
+            + (rect.width() / 2.0)
+            + rect.min.x,
+        -(rotated.im as f32 + view.offset.y) * (view.scale_factor * rect.width() / 1920.0)
+            + (rect.height() / 2.0)
+            + rect.min.y,
+    )
+}
+
 impl Point {
-    ///translates from cga2d coords to egui coords
-    fn to_pos2(&self, rect: &Rect, scale_factor: f32, offset: Vec2) -> Pos2 {
-        pos2(
-            (self.0.re as f32 + offset.x) * (scale_factor * rect.width() / 1920.0)
-                + (rect.width() / 2.0)
-                + rect.min.x,
-            -(self.0.im as f32 + offset.y) * (scale_factor * rect.width() / 1920.0)
-                + (rect.height() / 2.0)
-                + rect.min.y,
-        )
+    ///translates from cga2d coords to egui coords. in the orthographic view, the point is drawn on the hemisphere it
+    ///is on
+    pub fn to_pos2(&self, rect: &Rect, view: &View) -> Pos2 {
+        self.to_pos2_on(rect, view, SpherePoint::from_point(*self).z < 0.0)
     }
-    ///translates from egui coords to cga2d coords
-    fn from_pos2(pos: &Pos2, rect: &Rect, scale_factor: f32, offset: Vec2) -> Self {
-        Self(C64 {
-            re: (((pos.x - (rect.width() / 2.0)) * (1920.0 / (scale_factor * rect.width())))
-                - offset.x) as f64,
-            im: (((pos.y - (rect.height() / 2.0)) * (-1920.0 / (scale_factor * rect.width())))
-                - offset.y) as f64,
-        })
+    ///translates from cga2d coords to egui coords, drawing the point on the southern hemisphere if south is true in the
+    ///orthographic view
+    pub fn to_pos2_on(&self, rect: &Rect, view: &View, south: bool) -> Pos2 {
+        let plane = if view.orthographic {
+            orthographic(SpherePoint::from_point(*self), south)
+        } else {
+            self.0
+        };
+        plane_to_pos2(plane, rect, view)
+    }
+    ///translates from egui coords to cga2d coords. exactly undoes to_pos2, except for points on the far side of the
+    ///sphere in the orthographic view. returns None for the south pole in the orthographic view
+    pub fn from_pos2(pos: &Pos2, rect: &Rect, view: &View) -> Option<Self> {
+        let rotated = C64 {
+            re: (((pos.x - rect.min.x - (rect.width() / 2.0))
+                * (1920.0 / (view.scale_factor * rect.width())))
+                - view.offset.x) as f64,
+            im: (((pos.y - rect.min.y - (rect.height() / 2.0))
+                * (-1920.0 / (view.scale_factor * rect.width())))
+                - view.offset.y) as f64,
+        };
+        let unrotated = C64::from_angle(-view.rotation as f64) * rotated;
+        let plane = if view.mirrored {
+            C64 {
+                re: -unrotated.re,
+                im: unrotated.im,
+            }
+        } else {
+            unrotated
+        };
+        if view.orthographic {
+            from_orthographic(plane)
+        } else {
+            Some(Self(plane))
+        }
     }
 }
 
 impl Triangulation {
-    ///render the triangulation, according to a detail and a color. includes outlines
+    ///render the triangulation, according to a detail and a color, replaced by the color scheme. includes outlines
     pub fn render(
         &self,
         ui: &mut Ui,
         rect: &Rect,
-        scale_factor: f32,
-        offset: Vec2,
+        view: &View,
         width: f32,
         color: Color,
+        scheme: &ColorScheme,
     ) {
+        let fill = scheme.apply(color).to_egui();
+        let triangles = self
+            .inside
+            .iter()
+            .flat_map(|x| screen_triangles(*x, rect, view))
+            .collect::<Vec<[Pos2; 3]>>();
         let mut triangle_vertices: Vec<epaint::Vertex> = Vec::new(); //make a new vector of epaint vertices
-        for triangle in &self.inside {
+        for triangle in &triangles {
             //iterate over the triangles
-            for point in triangle {
+            for pos in triangle {
                 let vertex = epaint::Vertex {
-                    pos: point.to_pos2(rect, scale_factor, offset),
+                    pos: *pos,
                     uv: pos2(0.0, 0.0),
-                    color: color.to_egui(),
+                    color: fill,
                 };
                 triangle_vertices.push(vertex); //add the nondegenerate triangle vertices
             }
@@ -125,19 +410,115 @@ impl Triangulation {
         mesh.indices = (0..(triangle_vertices.len() as u32)).collect();
         mesh.vertices = triangle_vertices; //add all the vertices
         ui.painter().add(egui::Shape::Mesh(mesh.into())); //paint the triangles
+        if let Some(marking) = scheme.marking(color) {
+            self.render_marking(ui, rect, view, &triangles, marking, contrasting(fill));
+        }
 
         //now we render the outlines
-        for arc in &self.border {
-            ui.painter().add(PathShape::line(
-                arc.iter()
-                    .map(|x| x.to_pos2(rect, scale_factor, offset))
-                    .collect(),
-                Stroke::new(width, OUTLINE_COLOR),
-            ));
+        for line in self.border.iter().flat_map(|x| screen_lines(x, rect, view)) {
+            ui.painter()
+                .add(PathShape::line(line, Stroke::new(width, OUTLINE_COLOR)));
+        }
+    }
+    ///draw a marking over the triangles the triangulation is drawn as on the screen. patterns are lined up with the
+    ///screen, and clipped to the triangles
+    fn render_marking(
+        &self,
+        ui: &mut Ui,
+        rect: &Rect,
+        view: &View,
+        triangles: &[[Pos2; 3]],
+        marking: &Marking,
+        color: Color32,
+    ) {
+        let stroke = Stroke::new(1.5, color);
+        match marking {
+            Marking::Plain => {}
+            Marking::Stripes => stripes(ui, triangles, Vec2::new(1.0, 1.0), stroke),
+            Marking::Hatching => {
+                stripes(ui, triangles, Vec2::new(1.0, 1.0), stroke);
+                stripes(ui, triangles, Vec2::new(1.0, -1.0), stroke);
+            }
+            Marking::Dots => {
+                for [a, b, c] in triangles {
+                    let min = a.min(*b).min(*c);
+                    let max = a.max(*b).max(*c);
+                    //every point of the grid inside the bounding box of the triangle
+                    for i in (min.x / PATTERN_SPACING).ceil() as i32
+                        ..=(max.x / PATTERN_SPACING).floor() as i32
+                    {
+                        for j in (min.y / PATTERN_SPACING).ceil() as i32
+                            ..=(max.y / PATTERN_SPACING).floor() as i32
+                        {
+                            let dot = pos2(i as f32 * PATTERN_SPACING, j as f32 * PATTERN_SPACING);
+                            if in_triangle(dot, [*a, *b, *c]) {
+                                ui.painter()
+                                    .circle_filled(dot, PATTERN_SPACING / 5.0, color);
+                            }
+                        }
+                    }
+                }
+            }
+            Marking::Glyph(glyph) => {
+                ui.painter().text(
+                    self.center.to_pos2(rect, view),
+                    egui::Align2::CENTER_CENTER,
+                    glyph,
+                    FontId::proportional(GLYPH_SIZE),
+                    color,
+                );
+            }
+        }
+    }
+}
+
+///draw parallel lines, perpendicular to normal, clipped to the triangles
+fn stripes(ui: &mut Ui, triangles: &[[Pos2; 3]], normal: Vec2, stroke: Stroke) {
+    let normal = normal.normalized();
+    for triangle in triangles {
+        let heights = triangle.map(|x| x.to_vec2().dot(normal));
+        let (min, max) = (
+            heights.iter().copied().fold(f32::INFINITY, f32::min),
+            heights.iter().copied().fold(f32::NEG_INFINITY, f32::max),
+        );
+        for k in (min / PATTERN_SPACING).ceil() as i32..=(max / PATTERN_SPACING).floor() as i32 {
+            let h = k as f32 * PATTERN_SPACING;
+            //the points where the line crosses the edges of the triangle
+            let crossings = (0..3)
+                .filter_map(|i| {
+                    let (a, b) = (triangle[i], triangle[(i + 1) % 3]);
+                    let (ha, hb) = (heights[i], heights[(i + 1) % 3]);
+                    if (ha - h) * (hb - h) <= 0.0 && ha != hb {
+                        Some(a + (b - a) * ((h - ha) / (hb - ha)))
+                    } else {
+                        None
+                    }
+                })
+                .collect::<Vec<Pos2>>();
+            if let [a, .., b] = crossings[..] {
+                ui.painter().line_segment([a, b], stroke);
+            }
         }
     }
 }
 
+///check if a point is inside a triangle (or on its edge)
+fn in_triangle(p: Pos2, [a, b, c]: [Pos2; 3]) -> bool {
+    let side = |x: Pos2, y: Pos2| (y - x).x * (p - x).y - (y - x).y * (p - x).x;
+    let (d1, d2, d3) = (side(a, b), side(b, c), side(c, a));
+    !((d1 < 0.0 || d2 < 0.0 || d3 < 0.0) && (d1 > 0.0 || d2 > 0.0 || d3 > 0.0))
+}
+
+///black or white, whichever stands out more against the color
+fn contrasting(color: Color32) -> Color32 {
+    let luminance = 0.299 * color.r() as f32 + 0.587 * color.g() as f32 + 0.114 * color.b() as f32;
+    if luminance > 128.0 {
+        Color32::BLACK
+    } else {
+        Color32::WHITE
+    }
+}
+
 ///render a piece, with an outline
 impl RenderPiece {
     pub fn render(
@@ -146,73 +527,112 @@ impl RenderPiece {
         rect: &Rect,
         offset: Option<Turn>,
         outline_size: f32,
-        scale_factor: f32,
-        offset_pos: Vec2,
+        view: &View,
+        scheme: &ColorScheme,
     ) -> Result<(), String> {
-        //get the offset of the piece, base on if its in the animation_offset circle
-        let true_offset = if offset.is_none()
-            || self.piece.shape.in_circle(offset.unwrap().circle)
-                == Some(crate::complex::complex_circle::Contains::Inside)
-        {
-            offset
-        } else {
-            None
-        };
-        let true_piece = if let Some(twist) = true_offset {
-            //turn the piece around the offset
-            twist.turn_render_piece(self).unwrap_or(self.clone())
-        } else {
-            self.clone()
-        };
+        //turn the piece around the offset, if its in the animation_offset circle
+        let true_piece = self.offset_by(offset);
         for triangle in &true_piece.triangulations {
             //iterate over the triangles
-            triangle.render(
-                ui,
-                rect,
-                scale_factor,
-                offset_pos,
-                outline_size,
-                self.piece.color,
-            );
+            triangle.render(ui, rect, view, outline_size, self.piece.color, scheme);
         }
         Ok(())
     }
 }
 impl Puzzle {
-    ///render the puzzle, including outlines
+    ///render the puzzle, including outlines. if a filter is given (whether each piece is selected, and how the rest
+    ///are drawn), the pieces it doesn't select are drawn in its style
     pub fn render(
         &self,
         ui: &mut Ui,
         rect: &Rect,
         outline_width: f32,
-        scale_factor: f32,
-        offset: Vec2,
+        view: &View,
+        scheme: &ColorScheme,
+        filter: Option<(&[bool], FilterStyle)>,
     ) -> Result<(), String> {
         //get the offset from the animation_offset and anim_left
         let proper_offset = self
             .animation_offset
             .map(|off| off.mult(self.anim_left as f64));
-        for piece in &self.pieces {
-            //render each piece
-            piece.render(ui, rect, proper_offset, outline_width, scale_factor, offset)?;
+        for (i, piece) in self.pieces.iter().enumerate() {
+            //render each piece, unless the filter changes how it is drawn
+            match filter {
+                Some((selected, style)) if !selected.get(i).copied().unwrap_or(true) => {
+                    let color = piece.piece.color;
+                    let (filtered, width) = match style {
+                        FilterStyle::Hidden => continue,
+                        FilterStyle::Gray => (FILTERED_GRAY, outline_width),
+                        FilterStyle::Translucent => {
+                            let [r, g, b, a] =
+                                scheme.apply(color).to_egui().to_srgba_unmultiplied();
+                            (Color::Rgba([r, g, b, a / TRANSLUCENCY]), 0.0)
+                        }
+                    };
+                    //draw the piece with its color replaced, and without markings
+                    let scheme = ColorScheme {
+                        colors: HashMap::from([(color, filtered)]),
+                        markings: HashMap::new(),
+                    };
+                    piece.render(ui, rect, proper_offset, width, view, &scheme)?;
+                }
+                _ => piece.render(ui, rect, proper_offset, outline_width, view, scheme)?,
+            }
         }
         Ok(())
     }
-    ///processes a click input and does the corresponding turns
-    ///Ok(true) means the turn was completed
-    ///Ok(false) means that the turn was bandanged, or no turn was found
-    ///Err(e) means that an error was encountered
-    ///'cut' is whether the turn should cut
-    pub fn process_click(
-        &mut self,
+    ///get the index of the piece under a position on the screen, for inspecting pieces
+    pub fn piece_under(&self, rect: &Rect, pos: Pos2, view: &View) -> Option<usize> {
+        self.piece_at(Point::from_pos2(&pos, rect, view)?)
+    }
+    ///highlight the piece being inspected, and draw a dashed ghost outline of the piece of the solved state it belongs
+    ///in, if it was found
+    pub fn render_inspection(
+        &self,
+        ui: &mut Ui,
         rect: &Rect,
-        pos: Pos2,
-        left: bool,
-        scale_factor: f32,
-        offset: Vec2,
-        cut: bool,
-    ) -> Result<bool, String> {
-        let good_pos = Point::from_pos2(&pos, rect, scale_factor, offset); //the cga2d position of the click
+        outline_width: f32,
+        view: &View,
+        index: usize,
+        home: Option<usize>,
+    ) {
+        let width = 2.0 * outline_width.max(1.0);
+        if let Some(home) = home.and_then(|x| self.solved_state.get(x)) {
+            for points in home
+                .triangulations
+                .iter()
+                .flat_map(|x| &x.border)
+                .flat_map(|x| screen_lines(x, rect, view))
+            {
+                ui.painter().extend(egui::Shape::dashed_line(
+                    &points,
+                    Stroke::new(width, GHOST_COLOR),
+                    GHOST_DASH,
+                    GHOST_DASH,
+                ));
+            }
+        }
+        let Some(piece) = self.pieces.get(index) else {
+            return;
+        };
+        let offset = self
+            .animation_offset
+            .map(|off| off.mult(self.anim_left as f64));
+        for line in piece
+            .offset_by(offset)
+            .triangulations
+            .iter()
+            .flat_map(|x| &x.border)
+            .flat_map(|x| screen_lines(x, rect, view))
+        {
+            ui.painter()
+                .add(PathShape::line(line, Stroke::new(width, INSPECTED_COLOR)));
+        }
+    }
+    ///get the id of the turn whose circle is under a position on the screen
+    ///ties are broken by the radius, smaller radius gets priority (so that concentric circles work)
+    pub fn turn_id_at(&self, rect: &Rect, pos: Pos2, view: &View) -> Option<String> {
+        let good_pos = Point::from_pos2(&pos, rect, view)?; //the cga2d position of the click
         let mut min_dist: f64 = 10000.0;
         let mut min_rad: f64 = 10000.0;
         let mut correct_id: String = String::from("");
@@ -220,7 +640,6 @@ impl Puzzle {
             //iterate over the turns to find the closest one
             let (center, radius) = (turn.1.turn.circle.center, turn.1.turn.circle.r());
             //compare how close they are
-            //ties are broken by the radius, smaller radius gets priority (so that concentric circles work)
             if ((good_pos.dist(center).approx_cmp(&min_dist, PRECISION) == Ordering::Less)
                 || ((good_pos.dist(center).approx_eq(&min_dist, PRECISION))
                     && (radius.approx_cmp(&min_rad, PRECISION)) == Ordering::Less))
@@ -236,8 +655,26 @@ impl Puzzle {
         }
         if correct_id.is_empty() {
             //if no circle was found
-            return Ok(false);
+            return None;
         }
+        Some(correct_id)
+    }
+    ///processes a click input and does the corresponding turns
+    ///Ok(true) means the turn was completed
+    ///Ok(false) means that the turn was bandanged, or no turn was found
+    ///Err(e) means that an error was encountered
+    ///'cut' is whether the turn should cut
+    pub fn process_click(
+        &mut self,
+        rect: &Rect,
+        pos: Pos2,
+        left: bool,
+        view: &View,
+        cut: bool,
+    ) -> Result<bool, String> {
+        let Some(correct_id) = self.turn_id_at(rect, pos, view) else {
+            return Ok(false);
+        };
         if !left {
             //invert based on the type of click
             Ok(self.turn_id(&correct_id, cut, 1)?)
@@ -245,16 +682,26 @@ impl Puzzle {
             Ok(self.turn_id(&correct_id, cut, -1)?)
         }
     }
+    ///get the angle of a position on the screen around a turn (see Turn::angle_of), for dragging turns
+    pub fn pointer_angle(&self, id: &str, rect: &Rect, pos: Pos2, view: &View) -> Option<f64> {
+        Some(
+            self.turns
+                .get(id)?
+                .turn
+                .angle_of(Point::from_pos2(&pos, rect, view)?),
+        )
+    }
     ///get the circle hovered by the mouse
     ///picks amongst the valid turn circles of the puzzle
     pub fn get_hovered(
         &self,
         rect: &Rect,
         pos: Pos2,
-        scale_factor: f32,
-        offset: Vec2,
+        view: &View,
     ) -> Result<Option<Circle>, String> {
-        let good_pos = Point::from_pos2(&pos, rect, scale_factor, offset); //get the position
+        let Some(good_pos) = Point::from_pos2(&pos, rect, view) else {
+            return Ok(None); //the south pole, which no turn contains
+        };
         let mut min_dist: f64 = 10000.0;
         let mut min_rad: f64 = 10000.0;
         let mut correct_turn = None;
@@ -285,9 +732,40 @@ impl Puzzle {
     }
 }
 
+impl Thumbnails {
+    ///the thumbnail of a puzzle, read from its file the first time it is asked for. None if it has no (readable) thumbnail
+    fn get(
+        &mut self,
+        ctx: &egui::Context,
+        data: &PuzzleLoadingData,
+    ) -> Option<&egui::TextureHandle> {
+        let key = data.key();
+        self.0
+            .entry(key.clone())
+            .or_insert_with(|| {
+                let image =
+                    Image::from_png(&read_file_to_bytes(&data.thumbnail_path()).ok()?).ok()?;
+                Some(ctx.load_texture(
+                    format!("thumbnail/{key}"),
+                    egui::ColorImage::from_rgba_unmultiplied(
+                        [image.width as usize, image.height as usize],
+                        image.as_bytes(),
+                    ),
+                    egui::TextureOptions::LINEAR,
+                ))
+            })
+            .as_ref()
+    }
+}
+
 impl DataStorer {
-    ///render the data panel on the screen and read input for which button is clicked
-    pub fn render_panel(&mut self, ctx: &egui::Context) -> Result<Option<PuzzleLoadingData>, ()> {
+    ///render the data panel on the screen and read input for which button is clicked. the catalog picks which
+    ///puzzles are listed, and the arrow keys and enter pick one from the search box
+    pub fn render_panel(
+        &mut self,
+        ctx: &egui::Context,
+        catalog: &mut Catalog,
+    ) -> Result<Option<PuzzleLoadingData>, ()> {
         fn cmp_entries(a: &DefEntry, b: &DefEntry) -> Ordering {
             match (a, b) {
                 (DefEntry::Def(data_a), DefEntry::Def(data_b)) => {
@@ -298,15 +776,54 @@ impl DataStorer {
                 (DefEntry::Folder((na, _)), DefEntry::Folder((nb, _))) => OsString::cmp(na, nb),
             }
         }
-        fn render_def_entry(entry: &DefEntry, ui: &mut Ui) -> Option<PuzzleLoadingData> {
-            match entry {
-                DefEntry::Def(data) => {
-                    if ui.add(egui::Button::new(data.name.clone())).clicked() {
-                        Some(data.clone())
+        ///a button for a puzzle, next to a star that adds it to (or removes it from) the favorites.
+        ///'scroll' is whether to scroll to the button, when it was just picked with the arrow keys
+        fn render_def(
+            data: &PuzzleLoadingData,
+            ui: &mut Ui,
+            thumbnails: &mut Thumbnails,
+            catalog: &mut Catalog,
+            selected: bool,
+            scroll: bool,
+        ) -> Option<PuzzleLoadingData> {
+            let key = data.key();
+            ui.horizontal(|ui| {
+                let starred = catalog.favorites.contains(&key);
+                if ui
+                    .add(egui::Button::new(if starred { "★" } else { "☆" }).frame(false))
+                    .on_hover_text(if starred {
+                        "Remove from favorites"
                     } else {
-                        None
-                    }
+                        "Add to favorites"
+                    })
+                    .clicked()
+                {
+                    catalog.toggle_favorite(&key);
+                }
+                //show the thumbnail next to the name, if the puzzle has one
+                let button = match thumbnails.get(ui.ctx(), data) {
+                    Some(texture) => egui::Button::image_and_text(
+                        egui::Image::from_texture(texture).max_size(Vec2::splat(THUMBNAIL_SIZE)),
+                        data.name.clone(),
+                    ),
+                    None => egui::Button::new(data.name.clone()),
+                };
+                let response = ui.add(button.selected(selected));
+                if scroll {
+                    response.scroll_to_me(None);
                 }
+                response.clicked().then(|| data.clone())
+            })
+            .inner
+        }
+        fn render_def_entry(
+            entry: &DefEntry,
+            ui: &mut Ui,
+            thumbnails: &mut Thumbnails,
+            catalog: &mut Catalog,
+        ) -> Option<PuzzleLoadingData> {
+            match entry {
+                DefEntry::Def(data) => render_def(data, ui, thumbnails, catalog, false, false),
                 DefEntry::Folder((name, dirs)) => {
                     if let Some(x) = ui
                         .collapsing(name.to_string_lossy(), |inner_ui| {
@@ -315,7 +832,8 @@ impl DataStorer {
                                 dirs.clone().into_values().collect::<Vec<DefEntry>>();
                             sorted_dirs.sort_by(|a, b| cmp_entries(a, b));
                             for v in &sorted_dirs {
-                                if let Some(x) = render_def_entry(v, inner_ui) {
+                                if let Some(x) = render_def_entry(v, inner_ui, thumbnails, catalog)
+                                {
                                     ret = Some(x);
                                 }
                             }
@@ -330,6 +848,31 @@ impl DataStorer {
                 }
             }
         }
+        ///a menu picking one of the options, or none of them
+        fn option_combo(ui: &mut Ui, label: &str, picked: &mut Option<String>, options: &[String]) {
+            egui::ComboBox::from_label(label)
+                .selected_text(picked.clone().unwrap_or(String::from("Any")))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(picked, None, "Any");
+                    for option in options {
+                        ui.selectable_value(picked, Some(option.clone()), option);
+                    }
+                });
+        }
+        ///a checkbox turning a range filter on and off, and the bounds of the range when it is on
+        fn range_filter(ui: &mut Ui, label: &str, range: &mut Option<(usize, usize)>) {
+            ui.horizontal(|ui| {
+                let mut on = range.is_some();
+                if ui.checkbox(&mut on, label).changed() {
+                    *range = on.then_some((0, 1000));
+                }
+                if let Some((min, max)) = range {
+                    ui.add(egui::DragValue::new(min).range(0..=*max));
+                    ui.label("to");
+                    ui.add(egui::DragValue::new(max).range(*min..=usize::MAX));
+                }
+            });
+        }
         let panel = egui::SidePanel::new(egui::panel::Side::Right, "data_panel").resizable(false); //make the new panel
         Ok(panel
             .show(ctx, |ui| {
@@ -353,10 +896,143 @@ impl DataStorer {
                     let _ = self.load_keybinds("Configs/keybinds.kdl");
                 }
                 ui.separator();
+                let search = ui.add(
+                    egui::TextEdit::singleline(&mut catalog.search)
+                        .hint_text("Search puzzles (Ctrl+F)"),
+                );
+                if ui.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::F)) {
+                    search.request_focus();
+                }
+                if search.changed() {
+                    catalog.selected = 0;
+                }
+                let defs = self.puzzles.lock().unwrap().defs();
+                ui.collapsing("Filters and Sorting", |ui| {
+                    let mut authors = defs
+                        .iter()
+                        .flat_map(|x| x.authors.clone())
+                        .collect::<Vec<_>>();
+                    authors.sort();
+                    authors.dedup();
+                    let mut tags = defs
+                        .iter()
+                        .flat_map(|x| x.metadata.tags.clone())
+                        .collect::<Vec<_>>();
+                    tags.sort();
+                    tags.dedup();
+                    option_combo(ui, "Author", &mut catalog.author, &authors);
+                    option_combo(ui, "Tag", &mut catalog.tag, &tags);
+                    range_filter(ui, "Turns", &mut catalog.turns);
+                    range_filter(ui, "Pieces", &mut catalog.pieces);
+                    egui::ComboBox::from_label("Sort by")
+                        .selected_text(catalog.sort.name())
+                        .show_ui(ui, |ui| {
+                            for sort in CatalogSort::ALL {
+                                ui.selectable_value(&mut catalog.sort, sort, sort.name());
+                            }
+                        });
+                    if ui.button("Clear Filters").clicked() {
+                        catalog.clear();
+                    }
+                });
+                //the turns and pieces are only known once a puzzle is built, so build them on another thread, checking
+                //back until they all are
+                if catalog.needs_counts() {
+                    if self.count_in_background(DEF_PATH) {
+                        ui.label("Counting turns and pieces...");
+                        ctx.request_repaint_after(COUNT_CHECK_INTERVAL);
+                    }
+                    #[cfg(target_arch = "wasm32")]
+                    ui.label("Turns and pieces can't be counted on web");
+                }
+                let results = if catalog.is_filtering() {
+                    catalog.results(defs.clone(), &self.counts)
+                } else {
+                    Vec::new()
+                };
+                catalog.selected = catalog.selected.min(results.len().saturating_sub(1));
+                let mut moved = false;
+                if search.has_focus() {
+                    if ui.input(|i| i.key_pressed(egui::Key::ArrowDown))
+                        && catalog.selected + 1 < results.len()
+                    {
+                        catalog.selected += 1;
+                        moved = true;
+                    }
+                    if ui.input(|i| i.key_pressed(egui::Key::ArrowUp)) && catalog.selected > 0 {
+                        catalog.selected -= 1;
+                        moved = true;
+                    }
+                }
+                let mut picked = None;
+                if search.lost_focus() {
+                    if ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                        picked = results.get(catalog.selected).cloned();
+                    }
+                    if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                        catalog.search.clear();
+                    }
+                }
+                ui.separator();
+                let by_key = defs
+                    .iter()
+                    .map(|x| (x.key(), x))
+                    .collect::<HashMap<String, &PuzzleLoadingData>>();
                 Ok(egui::ScrollArea::vertical()
                     .show(ui, |ui| {
-                        let puzzles_real = self.puzzles.lock().unwrap();
-                        render_def_entry(&puzzles_real, ui)
+                        let mut ret = picked;
+                        for (title, keys) in [
+                            ("Favorites", catalog.favorites.clone()),
+                            ("Recently Played", catalog.recent.clone()),
+                        ] {
+                            let found = keys
+                                .iter()
+                                .filter_map(|x| by_key.get(x))
+                                .collect::<Vec<_>>();
+                            if found.is_empty() {
+                                continue;
+                            }
+                            egui::CollapsingHeader::new(title)
+                                .default_open(true)
+                                .show(ui, |ui| {
+                                    for data in found {
+                                        if let Some(x) = render_def(
+                                            data,
+                                            ui,
+                                            &mut self.thumbnails,
+                                            catalog,
+                                            false,
+                                            false,
+                                        ) {
+                                            ret = Some(x);
+                                        }
+                                    }
+                                });
+                        }
+                        if catalog.is_filtering() {
+                            ui.label(format!("{} puzzles found", results.len()));
+                            for (i, data) in results.iter().enumerate() {
+                                let selected = i == catalog.selected;
+                                if let Some(x) = render_def(
+                                    data,
+                                    ui,
+                                    &mut self.thumbnails,
+                                    catalog,
+                                    selected,
+                                    selected && moved,
+                                ) {
+                                    ret = Some(x);
+                                }
+                            }
+                        } else {
+                            let puzzles_real = self.puzzles.lock().unwrap();
+                            if let Some(x) =
+                                render_def_entry(&puzzles_real, ui, &mut self.thumbnails, catalog)
+                            {
+                                ret = Some(x);
+                            }
+                        }
+                        ret
                     })
                     .inner)
             })
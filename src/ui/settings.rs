@@ -0,0 +1,502 @@
+use std::collections::HashMap;
+
+use egui::{Vec2, vec2};
+use kdl::{KdlDocument, KdlNode, KdlValue};
+
+use crate::complex::c64::C64;
+use crate::complex::complex_circle::{ComplexCircle, Contains, OrientedCircle};
+use crate::complex::point::Point;
+use crate::hps::data_storer::catalog::RECENT_COUNT;
+use crate::hps::data_storer::puzzle_io::PuzzleIOData;
+use crate::puzzle::color::{Color, ColorScheme, Marking};
+use crate::puzzle::filter::{FilterStyle, PieceFilter};
+use crate::puzzle::region::Region;
+use crate::ui::render::View;
+
+///default scale factor
+pub const SCALE_FACTOR: f32 = 500.0;
+///default view of the puzzle
+pub const DEFAULT_VIEW: View = View {
+    scale_factor: SCALE_FACTOR,
+    offset: Vec2::ZERO,
+    rotation: 0.0,
+    mirrored: false,
+    orthographic: false,
+};
+///default animation speed
+pub const ANIMATION_SPEED: f64 = 5.0;
+///default outline width
+pub const OUTLINE_WIDTH: f32 = 5.0;
+///default log file name
+pub const LOG_PATH: &str = "logfile";
+///where the settings are saved on native
+#[cfg(not(target_arch = "wasm32"))]
+const SETTINGS_PATH: &str = "Configs/settings.kdl";
+///the key the settings are saved under in the eframe storage on web
+#[cfg(target_arch = "wasm32")]
+const SETTINGS_KEY: &str = "settings";
+
+#[derive(Debug, Clone)]
+///the user settings that are kept between sessions
+pub struct Settings {
+    pub animation_speed: f64,         //speed at which animations happen
+    pub outline_width: f32,           //the width of the outlines
+    pub view: View,                   //the zoom, pan and rotation of the puzzle
+    pub cut_on_turn: bool,            //whether or not turns should cut the puzzle
+    pub log_path: String,             //the path log files are loaded from/saved to
+    pub puzzle: Option<PuzzleIOData>, //the last opened puzzle and its progress
+    pub color_scheme: ColorScheme,    //the color scheme for every puzzle
+    pub show_markings: bool,          //whether markings are drawn over pieces to tell colors apart
+    pub puzzle_color_schemes: HashMap<String, ColorScheme>, //color schemes for single puzzles, by PuzzleData::key
+    pub piece_filters: HashMap<String, Vec<PieceFilter>>, //the filter presets of each puzzle, by PuzzleData::key
+    pub favorites: Vec<String>,                           //the starred puzzles, by PuzzleData::key
+    pub recent: Vec<String>, //the last puzzles played, most recent first, by PuzzleData::key
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            animation_speed: ANIMATION_SPEED,
+            outline_width: OUTLINE_WIDTH,
+            view: DEFAULT_VIEW,
+            cut_on_turn: false,
+            log_path: String::from(LOG_PATH),
+            puzzle: None,
+            color_scheme: ColorScheme::default(),
+            show_markings: false,
+            puzzle_color_schemes: HashMap::new(),
+            piece_filters: HashMap::new(),
+            favorites: Vec::new(),
+            recent: Vec::new(),
+        }
+    }
+}
+
+impl std::fmt::Display for Settings {
+    ///write the settings as a kdl document
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "animation_speed {:?}", self.animation_speed)?;
+        writeln!(f, "outline_width {:?}", self.outline_width)?;
+        writeln!(f, "scale_factor {:?}", self.view.scale_factor)?;
+        writeln!(
+            f,
+            "offset {:?} {:?}",
+            self.view.offset.x, self.view.offset.y
+        )?;
+        writeln!(f, "rotation {:?}", self.view.rotation)?;
+        writeln!(f, "mirrored #{}", self.view.mirrored)?;
+        writeln!(f, "orthographic #{}", self.view.orthographic)?;
+        writeln!(f, "cut_on_turn #{}", self.cut_on_turn)?;
+        writeln!(f, "log_path {:?}", self.log_path)?;
+        writeln!(f, "show_markings #{}", self.show_markings)?;
+        for (name, keys) in [("favorites", &self.favorites), ("recent", &self.recent)] {
+            if !keys.is_empty() {
+                let keys = keys.iter().map(|x| format!("{x:?}")).collect::<Vec<_>>();
+                writeln!(f, "{name} {}", keys.join(" "))?;
+            }
+        }
+        if let Some(puzzle) = &self.puzzle {
+            writeln!(f, "puzzle {{\n{}\n}}", puzzle.to_string())?;
+        }
+        f.write_str(&scheme_to_string(&self.color_scheme, None))?;
+        let mut puzzles = self.puzzle_color_schemes.iter().collect::<Vec<_>>();
+        puzzles.sort_by_key(|(key, _)| *key);
+        for (key, scheme) in puzzles {
+            f.write_str(&scheme_to_string(scheme, Some(key)))?;
+        }
+        let mut puzzles = self.piece_filters.iter().collect::<Vec<_>>();
+        puzzles.sort_by_key(|(key, _)| *key);
+        for (key, filters) in puzzles {
+            f.write_str(&filters_to_string(filters, key))?;
+        }
+        Ok(())
+    }
+}
+
+impl Settings {
+    ///read the settings from a kdl document. any setting that is missing or invalid is left at its default
+    pub fn from_string(string: &str) -> Self {
+        let mut settings = Self::default();
+        let Ok(kdl) = string.parse::<KdlDocument>() else {
+            return settings;
+        };
+        ///get the nth value of a node
+        fn value<'a>(kdl: &'a KdlDocument, name: &str, n: usize) -> Option<&'a KdlValue> {
+            Some(kdl.get(name)?.entries().get(n)?.value())
+        }
+        ///get the nth value of a node as a number, whether it was written as a float or an integer
+        fn number(kdl: &KdlDocument, name: &str, n: usize) -> Option<f64> {
+            let val = value(kdl, name, n)?;
+            val.as_float().or(val.as_integer().map(|x| x as f64))
+        }
+        if let Some(x) = number(&kdl, "animation_speed", 0) {
+            settings.animation_speed = x.clamp(1.0, 25.0);
+        }
+        if let Some(x) = number(&kdl, "outline_width", 0) {
+            settings.outline_width = (x as f32).clamp(0.0, 10.0);
+        }
+        if let Some(x) = number(&kdl, "scale_factor", 0) {
+            settings.view.scale_factor = (x as f32).clamp(10.0, 5000.0);
+        }
+        if let Some(x) = number(&kdl, "offset", 0)
+            && let Some(y) = number(&kdl, "offset", 1)
+        {
+            settings.view.offset = vec2(x as f32, y as f32);
+        }
+        if let Some(x) = number(&kdl, "rotation", 0) {
+            settings.view.rotate(x as f32);
+        }
+        if let Some(x) = value(&kdl, "mirrored", 0).and_then(KdlValue::as_bool) {
+            settings.view.mirrored = x;
+        }
+        if let Some(x) = value(&kdl, "orthographic", 0).and_then(KdlValue::as_bool) {
+            settings.view.orthographic = x;
+        }
+        if let Some(x) = value(&kdl, "cut_on_turn", 0).and_then(KdlValue::as_bool) {
+            settings.cut_on_turn = x;
+        }
+        if let Some(x) = value(&kdl, "log_path", 0).and_then(KdlValue::as_string) {
+            settings.log_path = x.to_string();
+        }
+        if let Some(x) = value(&kdl, "show_markings", 0).and_then(KdlValue::as_bool) {
+            settings.show_markings = x;
+        }
+        //every value of a node, as strings
+        let strings = |name: &str| {
+            kdl.get(name)
+                .map(|x| {
+                    x.entries()
+                        .iter()
+                        .filter_map(|x| x.value().as_string())
+                        .map(str::to_string)
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default()
+        };
+        settings.favorites = strings("favorites");
+        settings.recent = strings("recent");
+        settings.recent.truncate(RECENT_COUNT);
+        settings.puzzle = kdl
+            .get("puzzle")
+            .and_then(|x| x.children())
+            .and_then(PuzzleIOData::from_kdl);
+        for node in kdl.nodes().iter().filter(|x| x.name().value() == "colors") {
+            let scheme = scheme_from_kdl(node);
+            match node.entries().first().and_then(|x| x.value().as_string()) {
+                Some(key) => {
+                    settings
+                        .puzzle_color_schemes
+                        .insert(key.to_string(), scheme);
+                }
+                None => settings.color_scheme = scheme,
+            }
+        }
+        for node in kdl.nodes().iter().filter(|x| x.name().value() == "filters") {
+            if let Some(key) = node.entries().first().and_then(|x| x.value().as_string()) {
+                settings
+                    .piece_filters
+                    .insert(key.to_string(), filters_from_kdl(node));
+            }
+        }
+        settings
+    }
+    ///load the settings from the config file. if there is no config file, use the defaults
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load(_storage: Option<&dyn eframe::Storage>) -> Self {
+        match crate::hps::data_storer::io::read_file_to_string(SETTINGS_PATH) {
+            Ok(string) => Self::from_string(&string),
+            Err(_) => Self::default(),
+        }
+    }
+    ///load the settings from the eframe storage (local storage in the browser)
+    #[cfg(target_arch = "wasm32")]
+    pub fn load(storage: Option<&dyn eframe::Storage>) -> Self {
+        match storage.and_then(|x| x.get_string(SETTINGS_KEY)) {
+            Some(string) => Self::from_string(&string),
+            None => Self::default(),
+        }
+    }
+    ///save the settings to the config file if they have changed since they were last saved (see save_to)
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save(&self, last_saved: &mut String) -> Result<bool, String> {
+        self.save_to(std::path::Path::new(SETTINGS_PATH), last_saved)
+    }
+    ///save the settings to a file if they are different from the settings last saved, which are then updated.
+    ///returns whether the file was written
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save_to(&self, path: &std::path::Path, last_saved: &mut String) -> Result<bool, String> {
+        let string = self.to_string();
+        if string == *last_saved {
+            return Ok(false);
+        }
+        crate::hps::data_storer::io::write_string_to_file(path, &string)
+            .ok()
+            .ok_or("Error saving settings!".to_string())?;
+        *last_saved = string;
+        Ok(true)
+    }
+    ///save the settings to the eframe storage
+    #[cfg(target_arch = "wasm32")]
+    pub fn save(&self, storage: &mut dyn eframe::Storage) {
+        storage.set_string(SETTINGS_KEY, self.to_string());
+    }
+}
+
+///write a color as it appears in the settings
+fn color_to_string(color: Color) -> String {
+    color.to_hex().unwrap_or(String::from("none"))
+}
+
+///read a color written by color_to_string
+fn color_from_string(string: &str) -> Option<Color> {
+    match string {
+        "none" => Some(Color::None),
+        hex => Color::from_hex(hex),
+    }
+}
+
+///write a color scheme as a `colors` block, with a line for each changed color giving its replacement and marking.
+///the puzzle key is given if the scheme is for a single puzzle
+fn scheme_to_string(scheme: &ColorScheme, key: Option<&str>) -> String {
+    if scheme.is_empty() {
+        return String::new();
+    }
+    let mut lines = scheme
+        .colors
+        .keys()
+        .chain(scheme.markings.keys())
+        .map(|from| {
+            let mut line = format!("{:?}", color_to_string(*from));
+            if let Some(to) = scheme.colors.get(from) {
+                line += &format!(" {:?}", color_to_string(*to));
+            }
+            line += &match scheme.markings.get(from) {
+                Some(Marking::Plain) => String::from(" marking=\"plain\""),
+                Some(Marking::Stripes) => String::from(" marking=\"stripes\""),
+                Some(Marking::Dots) => String::from(" marking=\"dots\""),
+                Some(Marking::Hatching) => String::from(" marking=\"hatching\""),
+                Some(Marking::Glyph(glyph)) => format!(" glyph={glyph:?}"),
+                None => String::new(),
+            };
+            line
+        })
+        .collect::<Vec<String>>();
+    lines.sort();
+    lines.dedup();
+    let mut string = match key {
+        Some(key) => format!("colors {key:?} {{\n"),
+        None => String::from("colors {\n"),
+    };
+    for line in lines {
+        string += &format!("\t{line}\n");
+    }
+    string += "}\n";
+    string
+}
+
+///read a `colors` block, skipping anything that can't be understood
+fn scheme_from_kdl(node: &KdlNode) -> ColorScheme {
+    let mut scheme = ColorScheme::default();
+    for line in node.children().map(|x| x.nodes()).unwrap_or_default() {
+        let Some(from) = color_from_string(line.name().value()) else {
+            continue;
+        };
+        for entry in line.entries() {
+            let Some(value) = entry.value().as_string() else {
+                continue;
+            };
+            match entry.name().map(|x| x.value()) {
+                None => {
+                    if let Some(to) = color_from_string(value) {
+                        scheme.colors.insert(from, to);
+                    }
+                }
+                Some("marking") => {
+                    let marking = match value {
+                        "plain" => Marking::Plain,
+                        "stripes" => Marking::Stripes,
+                        "dots" => Marking::Dots,
+                        "hatching" => Marking::Hatching,
+                        _ => continue,
+                    };
+                    scheme.markings.insert(from, marking);
+                }
+                Some("glyph") => {
+                    scheme
+                        .markings
+                        .insert(from, Marking::Glyph(value.to_string()));
+                }
+                Some(_) => {}
+            }
+        }
+    }
+    scheme
+}
+
+///write the filter presets of a puzzle as a `filters` block, with a `filter` block for each preset
+fn filters_to_string(filters: &[PieceFilter], key: &str) -> String {
+    if filters.is_empty() {
+        return String::new();
+    }
+    let mut string = format!("filters {key:?} {{\n");
+    for filter in filters {
+        string += &format!("\tfilter {:?}", filter.name);
+        string += match filter.style {
+            FilterStyle::Hidden => " style=\"hidden\"",
+            FilterStyle::Translucent => " style=\"translucent\"",
+            FilterStyle::Gray => " style=\"gray\"",
+        };
+        if let Some(bind) = filter.key {
+            string += &format!(" key={:?}", bind.name());
+        }
+        string += " {\n";
+        for color in &filter.colors {
+            string += &format!("\t\tcolor {:?}\n", color_to_string(*color));
+        }
+        //a region of a single part is written as its circles, the empty region as `nowhere`, and others as a block
+        //for each part
+        match filter.region.0.as_slice() {
+            [] => string += "\t\tnowhere\n",
+            [part] => {
+                for circle in part {
+                    string += &format!("\t\t{}\n", circle_to_string(circle));
+                }
+            }
+            parts => {
+                for part in parts {
+                    string += "\t\tregion {\n";
+                    for circle in part {
+                        string += &format!("\t\t\t{}\n", circle_to_string(circle));
+                    }
+                    string += "\t\t}\n";
+                }
+            }
+        }
+        for orbit in &filter.orbits {
+            string += &format!("\t\torbit {orbit}\n");
+        }
+        string += "\t}\n";
+    }
+    string += "}\n";
+    string
+}
+
+///read a `filters` block, skipping anything that can't be understood
+fn filters_from_kdl(node: &KdlNode) -> Vec<PieceFilter> {
+    let mut filters = Vec::new();
+    for filter_node in node.children().map(|x| x.nodes()).unwrap_or_default() {
+        let Some(name) = filter_node
+            .entries()
+            .first()
+            .and_then(|x| x.value().as_string())
+        else {
+            continue;
+        };
+        let mut filter = PieceFilter::new(name);
+        if let Some(style) = filter_node.get("style").and_then(KdlValue::as_string) {
+            filter.style = match style {
+                "hidden" => FilterStyle::Hidden,
+                "gray" => FilterStyle::Gray,
+                _ => FilterStyle::Translucent,
+            };
+        }
+        filter.key = filter_node
+            .get("key")
+            .and_then(KdlValue::as_string)
+            .and_then(egui::Key::from_name);
+        //the circles of a region of a single part, and the parts of any other region
+        let mut circles = Vec::new();
+        let mut parts = Vec::new();
+        let mut nowhere = false;
+        for line in filter_node
+            .children()
+            .map(|x| x.nodes())
+            .unwrap_or_default()
+        {
+            let values = line
+                .entries()
+                .iter()
+                .map(|x| x.value())
+                .collect::<Vec<&KdlValue>>();
+            match line.name().value() {
+                "color" => {
+                    if let Some(color) = values
+                        .first()
+                        .and_then(|x| x.as_string())
+                        .and_then(color_from_string)
+                    {
+                        filter.colors.push(color);
+                    }
+                }
+                "circle" => circles.extend(circle_from_kdl(line)),
+                "nowhere" => nowhere = true,
+                "region" => parts.push(
+                    line.children()
+                        .map(|x| x.nodes())
+                        .unwrap_or_default()
+                        .iter()
+                        .filter(|x| x.name().value() == "circle")
+                        .filter_map(circle_from_kdl)
+                        .collect(),
+                ),
+                "orbit" => {
+                    if let Some(orbit) = values.first().and_then(|x| x.as_integer())
+                        && orbit >= 0
+                    {
+                        filter.orbits.push(orbit as usize);
+                    }
+                }
+                _ => {}
+            }
+        }
+        filter.region = if nowhere {
+            Region::nowhere()
+        } else if parts.is_empty() {
+            Region::from(circles)
+        } else {
+            Region(parts)
+        };
+        filters.push(filter);
+    }
+    filters
+}
+
+///write an oriented circle of a filter's region as a kdl node
+fn circle_to_string(circle: &OrientedCircle) -> String {
+    let center = circle.circ.center.0;
+    format!(
+        "circle {:?} {:?} {:?} {:?}",
+        center.re,
+        center.im,
+        circle.circ.r_sq,
+        if circle.ori == Contains::Outside {
+            "outside"
+        } else {
+            "inside"
+        }
+    )
+}
+
+///read an oriented circle of a filter's region from a kdl node, written as the center, the radius squared and the
+///orientation
+fn circle_from_kdl(node: &KdlNode) -> Option<OrientedCircle> {
+    let number = |n: usize| {
+        node.entries()
+            .get(n)
+            .map(|x| x.value())
+            .and_then(|x| x.as_float().or(x.as_integer().map(|x| x as f64)))
+    };
+    Some(OrientedCircle {
+        circ: ComplexCircle {
+            center: Point(C64 {
+                re: number(0)?,
+                im: number(1)?,
+            }),
+            r_sq: number(2)?,
+        },
+        ori: match node.entries().get(3).and_then(|x| x.value().as_string()) {
+            Some("outside") => Contains::Outside,
+            _ => Contains::Inside,
+        },
+    })
+}
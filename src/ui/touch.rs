@@ -0,0 +1,92 @@
+use egui::{MultiTouchInfo, Pos2, Vec2};
+
+///how far (in radians) two fingers have to twist before the gesture is read as a turn
+const ROTATION_THRESHOLD: f32 = 0.2;
+///how far two fingers have to pinch (as a proportion) before the gesture is read as a zoom
+const ZOOM_THRESHOLD: f32 = 0.1;
+///how far (in points) two fingers have to move before the gesture is read as a pan
+const PAN_THRESHOLD: f32 = 20.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+///what a two finger gesture is being used for. decided by whichever motion passes its threshold first
+enum TouchGesture {
+    ///the motion so far, before a decision has been made
+    Undecided { rotation: f32, zoom: f32, pan: Vec2 },
+    ///pinching and moving zooms and pans the view
+    Camera,
+    ///twisting turns the circle the gesture started on
+    Turn,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+///what the app should do in response to the touches on a frame.
+///rotations are in screen space, so positive is clockwise on screen
+pub enum TouchAction {
+    ///multiply the zoom and move the view
+    Camera { zoom: f32, pan: Vec2 },
+    ///start turning the circle under pos, already twisted by rotation
+    StartTurn { pos: Pos2, rotation: f32 },
+    ///twist the current turn further
+    Turn(f32),
+    ///the fingers were lifted, so finish the turn
+    EndTurn,
+}
+
+#[derive(Debug, Default)]
+///tracks two finger gestures across frames
+pub struct TouchState {
+    gesture: Option<TouchGesture>,
+}
+
+impl TouchState {
+    ///update the gesture with this frame's touch info (from egui::InputState::multi_touch)
+    pub fn update(&mut self, info: Option<MultiTouchInfo>) -> Option<TouchAction> {
+        let Some(info) = info else {
+            //all but one finger was lifted, so the gesture is over
+            return match self.gesture.take() {
+                Some(TouchGesture::Turn) => Some(TouchAction::EndTurn),
+                _ => None,
+            };
+        };
+        match self.gesture.unwrap_or(TouchGesture::Undecided {
+            rotation: 0.0,
+            zoom: 1.0,
+            pan: Vec2::ZERO,
+        }) {
+            TouchGesture::Undecided {
+                rotation,
+                zoom,
+                pan,
+            } => {
+                let (rotation, zoom, pan) = (
+                    rotation + info.rotation_delta,
+                    zoom * info.zoom_delta,
+                    pan + info.translation_delta,
+                );
+                //once a motion passes its threshold, catch up on everything that happened before the decision
+                if rotation.abs() > ROTATION_THRESHOLD {
+                    self.gesture = Some(TouchGesture::Turn);
+                    Some(TouchAction::StartTurn {
+                        pos: info.start_pos,
+                        rotation,
+                    })
+                } else if (zoom - 1.0).abs() > ZOOM_THRESHOLD || pan.length() > PAN_THRESHOLD {
+                    self.gesture = Some(TouchGesture::Camera);
+                    Some(TouchAction::Camera { zoom, pan })
+                } else {
+                    self.gesture = Some(TouchGesture::Undecided {
+                        rotation,
+                        zoom,
+                        pan,
+                    });
+                    None
+                }
+            }
+            TouchGesture::Camera => Some(TouchAction::Camera {
+                zoom: info.zoom_delta,
+                pan: info.translation_delta,
+            }),
+            TouchGesture::Turn => Some(TouchAction::Turn(info.rotation_delta)),
+        }
+    }
+}